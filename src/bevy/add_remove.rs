@@ -1,22 +1,28 @@
 use bevy_ecs::prelude::*;
 
+use crate::EcsBenchmark;
+
 struct A(f32);
 struct B(f32);
 
 pub struct Benchmark(World, Vec<Entity>);
 
-impl Benchmark {
-    pub fn new() -> Self {
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
         let mut world = World::default();
 
         let entities = world
-            .spawn_batch((0..10000).map(|_| (A(0.0),)))
+            .spawn_batch((0..size).map(|_| (A(0.0),)))
             .collect::<Vec<_>>();
 
         Self(world, entities)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         for entity in &self.1 {
             self.0.entity_mut(*entity).insert(B(0.0));
         }
@@ -25,4 +31,10 @@ impl Benchmark {
             self.0.entity_mut(*entity).remove::<B>();
         }
     }
+
+    fn checksum(&mut self) -> f64 {
+        let a = self.0.query::<&A>().iter(&self.0).count();
+        let b = self.0.query::<&B>().iter(&self.0).count();
+        (a + b) as f64
+    }
 }
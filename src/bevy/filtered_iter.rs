@@ -0,0 +1,58 @@
+use bevy_ecs::prelude::*;
+use cgmath::*;
+
+use crate::EcsBenchmark;
+
+#[derive(Copy, Clone)]
+struct Position(Vector3<f32>);
+
+#[derive(Copy, Clone)]
+struct Velocity(Vector3<f32>);
+
+#[derive(Copy, Clone)]
+struct Drag(f32);
+
+#[derive(Copy, Clone)]
+struct Frozen;
+
+pub struct Benchmark(World);
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = World::new();
+        for i in 0..size {
+            let mut entity = world.spawn();
+            entity.insert_bundle((Position(Vector3::unit_x()), Velocity(Vector3::unit_x())));
+            if i % 3 == 0 {
+                entity.insert(Drag(0.5));
+            }
+            if i % 4 == 0 {
+                entity.insert(Frozen);
+            }
+        }
+
+        Self(world)
+    }
+
+    fn run(&mut self) {
+        let mut query = self
+            .0
+            .query_filtered::<(&mut Position, &Velocity, Option<&Drag>), Without<Frozen>>();
+
+        for (mut position, velocity, drag) in query.iter_mut(&mut self.0) {
+            position.0 += velocity.0 * (1.0 - drag.map_or(0.0, |drag| drag.0));
+        }
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let mut query = self.0.query::<&Position>();
+        query
+            .iter(&self.0)
+            .map(|position| position.0.x as f64)
+            .sum()
+    }
+}
@@ -2,6 +2,8 @@ use bevy_ecs::prelude::*;
 use bevy_tasks::TaskPool;
 use cgmath::*;
 
+use crate::EcsBenchmark;
+
 #[derive(Copy, Clone)]
 struct Position(Vector3<f32>);
 
@@ -13,11 +15,15 @@ struct Velocity(Vector3<f32>);
 
 pub struct Benchmark(World);
 
-impl Benchmark {
-    pub fn new() -> Self {
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
         let mut world = World::default();
 
-        world.spawn_batch((0..1000).map(|_| {
+        world.spawn_batch((0..size).map(|_| {
             (
                 Matrix4::<f32>::from_angle_x(Rad(1.2)),
                 Position(Vector3::unit_x()),
@@ -29,7 +35,7 @@ impl Benchmark {
         Self(world)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         let task_pool = TaskPool::new();
         let mut query = self.0.query::<(&mut Position, &mut Matrix4<f32>)>();
 
@@ -41,4 +47,12 @@ impl Benchmark {
             pos.0 = mat.transform_vector(pos.0);
         });
     }
+
+    fn checksum(&mut self) -> f64 {
+        let mut query = self.0.query::<&Position>();
+        query
+            .iter(&self.0)
+            .map(|position| (position.0.x + position.0.y + position.0.z) as f64)
+            .sum()
+    }
 }
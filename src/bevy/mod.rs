@@ -1,6 +1,12 @@
 pub mod add_remove;
+pub mod despawn;
+pub mod filtered_iter;
 pub mod frag_iter;
 pub mod heavy_compute;
+pub mod random_access;
 pub mod schedule;
 pub mod simple_insert;
 pub mod simple_iter;
+pub mod transform_propagation;
+
+pub const NAME: &str = "bevy";
@@ -1,5 +1,7 @@
 use bevy_ecs::{prelude::*, schedule::Schedule};
 
+use crate::EcsBenchmark;
+
 struct A(f32);
 struct B(f32);
 struct C(f32);
@@ -26,17 +28,21 @@ fn ce(mut query: Query<(&mut C, &mut E)>) {
 
 pub struct Benchmark(World, Schedule);
 
-impl Benchmark {
-    pub fn new() -> Self {
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
         let mut world = World::default();
 
-        world.spawn_batch((0..10000).map(|_| (A(0.0), B(0.0))));
+        world.spawn_batch((0..size).map(|_| (A(1.0), B(2.0))));
 
-        world.spawn_batch((0..10000).map(|_| (A(0.0), B(0.0), C(0.0))));
+        world.spawn_batch((0..size).map(|_| (A(1.0), B(2.0), C(3.0))));
 
-        world.spawn_batch((0..10000).map(|_| (A(0.0), B(0.0), C(0.0), D(0.0))));
+        world.spawn_batch((0..size).map(|_| (A(1.0), B(2.0), C(3.0), D(4.0))));
 
-        world.spawn_batch((0..10000).map(|_| (A(0.0), B(0.0), C(0.0), E(0.0))));
+        world.spawn_batch((0..size).map(|_| (A(1.0), B(2.0), C(3.0), E(5.0))));
 
         let mut schedule = Schedule::default();
         schedule.add_stage("main", SystemStage::parallel());
@@ -47,7 +53,15 @@ impl Benchmark {
         Self(world, schedule)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         self.1.run(&mut self.0);
     }
+
+    fn checksum(&mut self) -> f64 {
+        let b: f32 = self.0.query::<&B>().iter(&self.0).map(|b| b.0).sum();
+        let c: f32 = self.0.query::<&C>().iter(&self.0).map(|c| c.0).sum();
+        let d: f32 = self.0.query::<&D>().iter(&self.0).map(|d| d.0).sum();
+        let e: f32 = self.0.query::<&E>().iter(&self.0).map(|e| e.0).sum();
+        (b + c + d + e) as f64
+    }
 }
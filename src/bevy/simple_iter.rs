@@ -1,6 +1,8 @@
 use bevy_ecs::prelude::*;
 use cgmath::*;
 
+use crate::EcsBenchmark;
+
 #[derive(Copy, Clone)]
 struct Transform(Matrix4<f32>);
 
@@ -15,10 +17,14 @@ struct Velocity(Vector3<f32>);
 
 pub struct Benchmark(World);
 
-impl Benchmark {
-    pub fn new() -> Self {
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
         let mut world = World::new();
-        world.spawn_batch((0..1_000_000).map(|_| {
+        world.spawn_batch((0..size).map(|_| {
             (
                 Transform(Matrix4::from_scale(1.0)),
                 Position(Vector3::unit_x()),
@@ -30,11 +36,19 @@ impl Benchmark {
         Self(world)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         let mut query = self.0.query::<(&Velocity, &mut Position)>();
 
         for (velocity, mut position) in query.iter_mut(&mut self.0) {
             position.0 += velocity.0;
         }
     }
+
+    fn checksum(&mut self) -> f64 {
+        let mut query = self.0.query::<&Position>();
+        query
+            .iter(&self.0)
+            .map(|position| position.0.x as f64)
+            .sum()
+    }
 }
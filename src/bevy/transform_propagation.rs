@@ -0,0 +1,75 @@
+use bevy_ecs::prelude::*;
+use cgmath::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::EcsBenchmark;
+
+const DEPTH: usize = 10;
+
+#[derive(Copy, Clone)]
+struct Transform(Matrix4<f32>);
+
+#[derive(Copy, Clone)]
+struct GlobalTransform(Matrix4<f32>);
+
+struct Children(Vec<Entity>);
+
+pub struct Benchmark(World, Vec<Entity>);
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = World::new();
+        let mut rng = StdRng::seed_from_u64(0);
+        let levels = (0..DEPTH)
+            .map(|level| {
+                world
+                    .spawn_batch((0..size).map(|i| {
+                        let offset = Vector3::new(i as f32, level as f32, 0.0);
+                        (
+                            Transform(Matrix4::from_translation(offset)),
+                            GlobalTransform(Matrix4::identity()),
+                        )
+                    }))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        for level in 1..DEPTH {
+            let mut children = vec![Vec::new(); size];
+            for &child in &levels[level] {
+                children[rng.gen_range(0..size)].push(child);
+            }
+            for (&parent, children) in levels[level - 1].iter().zip(children) {
+                if !children.is_empty() {
+                    world.entity_mut(parent).insert(Children(children));
+                }
+            }
+        }
+
+        Self(world, levels.into_iter().next().unwrap())
+    }
+
+    fn run(&mut self) {
+        let world = &mut self.0;
+        let mut stack: Vec<(Entity, Matrix4<f32>)> = self.1.iter().map(|&root| (root, Matrix4::identity())).collect();
+        while let Some((entity, parent)) = stack.pop() {
+            let composed = parent * world.get::<Transform>(entity).unwrap().0;
+            world.get_mut::<GlobalTransform>(entity).unwrap().0 = composed;
+            if let Some(children) = world.get::<Children>(entity) {
+                stack.extend(children.0.iter().map(|&child| (child, composed)));
+            }
+        }
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let mut query = self.0.query::<&GlobalTransform>();
+        query
+            .iter(&self.0)
+            .map(|global| global.0.w.x as f64)
+            .sum()
+    }
+}
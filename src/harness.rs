@@ -0,0 +1,88 @@
+/// A single backend's implementation of a benchmark scenario.
+///
+/// `setup` and `teardown` run outside of the timed section, only `run` is measured.
+pub trait EcsBenchmark {
+    /// Name of the backend, used as the criterion function id.
+    fn name() -> &'static str
+    where
+        Self: Sized;
+
+    /// Builds the dataset, `size` is the scenario specific dataset size, usually the
+    /// number of entities.
+    fn setup(size: usize) -> Self
+    where
+        Self: Sized;
+
+    fn run(&mut self);
+
+    /// Summarises the state left behind by `run`.
+    ///
+    /// Every backend of a scenario has to arrive at the same value after a single `run`,
+    /// see [`Scenario::verify`].
+    fn checksum(&mut self) -> f64;
+
+    fn teardown(&mut self) {}
+}
+
+pub struct Backend {
+    pub name: &'static str,
+    pub setup: fn(usize) -> Box<dyn EcsBenchmark>,
+}
+
+fn boxed<B: EcsBenchmark + 'static>(size: usize) -> Box<dyn EcsBenchmark> {
+    Box::new(B::setup(size))
+}
+
+/// A benchmark scenario together with every backend implementing it.
+pub struct Scenario {
+    pub name: &'static str,
+    /// Number of elements processed by a single `run` for a given dataset size, reported
+    /// as criterion throughput.
+    pub elements: fn(usize) -> u64,
+    /// Dataset sizes swept by default.
+    pub sizes: Vec<usize>,
+    pub backends: Vec<Backend>,
+}
+
+impl Scenario {
+    pub fn new(name: &'static str, elements: fn(usize) -> u64, sizes: &[usize]) -> Self {
+        Self {
+            name,
+            elements,
+            sizes: sizes.to_vec(),
+            backends: Vec::new(),
+        }
+    }
+
+    pub fn register<B: EcsBenchmark + 'static>(mut self) -> Self {
+        self.backends.push(Backend {
+            name: B::name(),
+            setup: boxed::<B>,
+        });
+        self
+    }
+
+    /// Runs every backend once at the given size and compares its checksum against the first registered
+    /// backend, which serves as the reference implementation.
+    ///
+    /// Panics on the first backend that diverges, so that a backend skipping work can't
+    /// post a misleadingly good number.
+    pub fn verify(&self, size: usize) {
+        let mut reference = None;
+        for backend in &self.backends {
+            let mut bench = (backend.setup)(size);
+            bench.run();
+            let checksum = bench.checksum();
+            bench.teardown();
+
+            let (reference_name, expected) = *reference.get_or_insert((backend.name, checksum));
+            let tolerance = 1e-3 * f64::max(expected.abs(), 1.0);
+            if (checksum - expected).abs() > tolerance {
+                panic!(
+                    "{}/{}: backend `{}` produced checksum {} but `{}` produced {}",
+                    self.name, size, backend.name, checksum, reference_name, expected
+                );
+            }
+        }
+    }
+}
@@ -1,22 +1,28 @@
 use hecs::*;
 
+use crate::EcsBenchmark;
+
 struct A(f32);
 struct B(f32);
 
 pub struct Benchmark(World, Vec<Entity>);
 
-impl Benchmark {
-    pub fn new() -> Self {
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
         let mut world = World::default();
 
         let entities = world
-            .spawn_batch((0..10000).map(|_| (A(0.0),)))
+            .spawn_batch((0..size).map(|_| (A(0.0),)))
             .collect::<Vec<_>>();
 
         Self(world, entities)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         for entity in &self.1 {
             self.0.insert_one(*entity, B(0.0)).unwrap();
         }
@@ -25,4 +31,10 @@ impl Benchmark {
             self.0.remove_one::<B>(*entity).unwrap();
         }
     }
+
+    fn checksum(&mut self) -> f64 {
+        let a = self.0.query::<&A>().iter().count();
+        let b = self.0.query::<&B>().iter().count();
+        (a + b) as f64
+    }
 }
@@ -0,0 +1,40 @@
+use hecs::*;
+
+use crate::EcsBenchmark;
+
+struct A(f32);
+struct B(f32);
+
+pub struct Benchmark(World, Vec<Entity>);
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = World::default();
+
+        let entities = world
+            .spawn_batch((0..size).map(|_| (A(0.0), B(0.0))))
+            .collect::<Vec<_>>();
+
+        Self(world, entities)
+    }
+
+    fn run(&mut self) {
+        let size = self.1.len();
+        for entity in self.1.drain(..) {
+            self.0.despawn(entity).unwrap();
+        }
+
+        self.1
+            .extend(self.0.spawn_batch((0..size).map(|_| (A(0.0), B(0.0)))));
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let a = self.0.query::<&A>().iter().count();
+        let b = self.0.query::<&B>().iter().count();
+        (a + b) as f64
+    }
+}
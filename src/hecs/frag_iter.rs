@@ -1,10 +1,12 @@
 use hecs::*;
 
+use crate::EcsBenchmark;
+
 macro_rules! create_entities {
-    ($world:ident; $( $variants:ident ),*) => {
+    ($world:ident, $size:expr; $( $variants:ident ),*) => {
         $(
             struct $variants(f32);
-            $world.spawn_batch((0..20).map(|_| ($variants(0.0), Data(1.0))));
+            $world.spawn_batch((0..$size).map(|_| ($variants(0.0), Data(1.0))));
         )*
     };
 }
@@ -13,18 +15,30 @@ struct Data(f32);
 
 pub struct Benchmark(World);
 
-impl Benchmark {
-    pub fn new() -> Self {
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
         let mut world = World::default();
 
-        create_entities!(world; A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z);
+        create_entities!(world, size; A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z);
 
         Self(world)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         for (_, mut data) in self.0.query_mut::<&mut Data>() {
             data.0 *= 2.0;
         }
     }
+
+    fn checksum(&mut self) -> f64 {
+        self.0
+            .query::<&Data>()
+            .iter()
+            .map(|(_, data)| data.0 as f64)
+            .sum()
+    }
 }
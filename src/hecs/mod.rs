@@ -1,7 +1,11 @@
 pub mod add_remove;
+pub mod despawn;
 pub mod frag_iter;
 pub mod heavy_compute;
+pub mod random_access;
 pub mod simple_insert;
 pub mod simple_iter;
 pub mod serialize_binary;
-pub mod serialize_text;
\ No newline at end of file
+pub mod serialize_text;
+
+pub const NAME: &str = "hecs";
@@ -0,0 +1,48 @@
+use cgmath::*;
+use hecs::*;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::EcsBenchmark;
+
+#[derive(Copy, Clone)]
+struct Position(Vector3<f32>);
+
+#[derive(Copy, Clone)]
+struct Velocity(Vector3<f32>);
+
+pub struct Benchmark(World, Vec<Entity>, f64);
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = World::new();
+        let mut entities = world
+            .spawn_batch((0..size).map(|i| {
+                (
+                    Position(Vector3::new(i as f32, 0.0, 0.0)),
+                    Velocity(Vector3::unit_x()),
+                )
+            }))
+            .collect::<Vec<_>>();
+        entities.shuffle(&mut StdRng::seed_from_u64(0));
+
+        Self(world, entities, 0.0)
+    }
+
+    fn run(&mut self) {
+        let mut sum = 0.0;
+        for &entity in &self.1 {
+            let position = self.0.get::<Position>(entity).unwrap();
+            let velocity = self.0.get::<Velocity>(entity).unwrap();
+            sum += (position.0.x + velocity.0.x) as f64;
+        }
+        self.2 = sum;
+    }
+
+    fn checksum(&mut self) -> f64 {
+        self.2
+    }
+}
@@ -1,6 +1,9 @@
+use bincode::Options;
 use hecs::{serialize::column::*, *};
 use serde::{de::SeqAccess, ser::SerializeTuple, Deserialize, Serialize};
 
+use crate::EcsBenchmark;
+
 #[derive(Default, Copy, Clone, Serialize, Deserialize)]
 struct Transform([f32; 16]);
 
@@ -142,39 +145,59 @@ impl DeserializeContext for DeContext {
     }
 }
 
-pub struct Benchmark(World);
+/// Fixed size integers, like `bincode::serialize` as used by the other backends.
+fn options() -> impl Options {
+    bincode::DefaultOptions::new().with_fixint_encoding().allow_trailing_bytes()
+}
+
+pub struct Benchmark(World, Option<World>);
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
 
-impl Benchmark {
-    pub fn new() -> Self {
+    fn setup(size: usize) -> Self {
         let mut world = World::new();
 
-        world.spawn_batch((0..1000).map(|_| {
+        world.spawn_batch((0..size).map(|i| {
             (
                 Transform::default(),
-                Position::default(),
+                Position { x: i as f32, y: 0.0, z: 0.0 },
                 Rotation::default(),
-                Velocity::default(),
+                Velocity { x: 1.0, y: 0.0, z: 0.0 },
             )
         }));
 
-        Self(world)
+        Self(world, None)
     }
 
-    pub fn run(&mut self) {
-        let Self(world) = self;
+    fn run(&mut self) {
+        let Self(world, deserialized) = self;
         let mut encoded = Vec::new();
         serialize(
             &world,
             &mut SerContext,
-            &mut bincode::Serializer::new(&mut encoded, bincode::options()),
+            &mut bincode::Serializer::new(&mut encoded, options()),
         )
         .unwrap();
-        deserialize(
+        let restored = deserialize(
             &mut DeContext {
                 components: Vec::new(),
             },
-            &mut bincode::Deserializer::from_slice(&encoded, bincode::options()),
+            &mut bincode::Deserializer::from_slice(&encoded, options()),
         )
         .unwrap();
+        *deserialized = Some(restored);
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let world = self.1.as_ref().unwrap();
+        let mut query = world.query::<(&Position, &Velocity)>();
+        query.iter().map(|(_, (pos, vel))| (pos.x + vel.x) as f64).sum()
+    }
+
+    fn teardown(&mut self) {
+        self.1 = None;
     }
 }
@@ -1,6 +1,8 @@
 use hecs::{serialize::row::*, *};
 use serde::{de::MapAccess, ser::SerializeMap, Deserialize, Serialize};
 
+use crate::EcsBenchmark;
+
 #[derive(Default, Copy, Clone, Serialize, Deserialize)]
 struct Transform([f32; 16]);
 
@@ -80,26 +82,30 @@ impl DeserializeContext for DeContext {
     }
 }
 
-pub struct Benchmark(World);
+pub struct Benchmark(World, Option<World>);
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
 
-impl Benchmark {
-    pub fn new() -> Self {
+    fn setup(size: usize) -> Self {
         let mut world = World::new();
 
-        world.spawn_batch((0..1000).map(|_| {
+        world.spawn_batch((0..size).map(|i| {
             (
                 Transform::default(),
-                Position::default(),
+                Position { x: i as f32, y: 0.0, z: 0.0 },
                 Rotation::default(),
-                Velocity::default(),
+                Velocity { x: 1.0, y: 0.0, z: 0.0 },
             )
         }));
 
-        Self(world)
+        Self(world, None)
     }
 
-    pub fn run(&mut self) {
-        let Self(world) = self;
+    fn run(&mut self) {
+        let Self(world, deserialized) = self;
         let mut encoded = Vec::new();
         serialize(
             &world,
@@ -107,10 +113,21 @@ impl Benchmark {
             &mut ron::Serializer::new(&mut encoded, None, false).unwrap(),
         )
         .unwrap();
-        deserialize(
+        let restored = deserialize(
             &mut DeContext,
             &mut ron::Deserializer::from_bytes(&encoded).unwrap(),
         )
         .unwrap();
+        *deserialized = Some(restored);
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let world = self.1.as_ref().unwrap();
+        let mut query = world.query::<(&Position, &Velocity)>();
+        query.iter().map(|(_, (pos, vel))| (pos.x + vel.x) as f64).sum()
+    }
+
+    fn teardown(&mut self) {
+        self.1 = None;
     }
 }
@@ -1,6 +1,8 @@
 use cgmath::*;
 use hecs::*;
 
+use crate::EcsBenchmark;
+
 #[derive(Copy, Clone)]
 struct Transform(Matrix4<f32>);
 
@@ -13,16 +15,20 @@ struct Rotation(Vector3<f32>);
 #[derive(Copy, Clone)]
 struct Velocity(Vector3<f32>);
 
-pub struct Benchmark;
+pub struct Benchmark(usize, Option<World>);
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
 
-impl Benchmark {
-    pub fn new() -> Self {
-        Self
+    fn setup(size: usize) -> Self {
+        Self(size, None)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         let mut world = World::new();
-        world.spawn_batch((0..1_000_000).map(|_| {
+        world.spawn_batch((0..self.0).map(|_| {
             (
                 Transform(Matrix4::from_scale(1.0)),
                 Position(Vector3::unit_x()),
@@ -30,5 +36,20 @@ impl Benchmark {
                 Velocity(Vector3::unit_x()),
             )
         }));
+
+        self.1 = Some(world);
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let world = self.1.as_ref().unwrap();
+        world
+            .query::<&Position>()
+            .iter()
+            .map(|(_, position)| position.0.x as f64)
+            .sum()
+    }
+
+    fn teardown(&mut self) {
+        self.1 = None;
     }
 }
@@ -1,6 +1,8 @@
 use cgmath::*;
 use hecs::*;
 
+use crate::EcsBenchmark;
+
 #[derive(Copy, Clone)]
 struct Transform(Matrix4<f32>);
 
@@ -15,10 +17,14 @@ struct Velocity(Vector3<f32>);
 
 pub struct Benchmark(World);
 
-impl Benchmark {
-    pub fn new() -> Self {
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
         let mut world = World::new();
-        world.spawn_batch((0..1_000_000).map(|_| {
+        world.spawn_batch((0..size).map(|_| {
             (
                 Transform(Matrix4::from_scale(1.0)),
                 Position(Vector3::unit_x()),
@@ -30,9 +36,17 @@ impl Benchmark {
         Self(world)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         for (_, (velocity, position)) in self.0.query_mut::<(&Velocity, &mut Position)>() {
             position.0 += velocity.0;
         }
     }
+
+    fn checksum(&mut self) -> f64 {
+        self.0
+            .query::<&Position>()
+            .iter()
+            .map(|(_, position)| position.0.x as f64)
+            .sum()
+    }
 }
@@ -1,20 +1,26 @@
 use legion::*;
 
+use crate::EcsBenchmark;
+
 struct A(f32);
 struct B(f32);
 
 pub struct Benchmark(World, Vec<Entity>);
 
-impl Benchmark {
-    pub fn new() -> Self {
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
         let mut world = World::default();
 
-        let entities = world.extend((0..10000).map(|_| (A(0.0),))).to_vec();
+        let entities = world.extend((0..size).map(|_| (A(0.0),))).to_vec();
 
         Self(world, entities)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         for entity in &self.1 {
             self.0.entry(*entity).unwrap().add_component(B(0.0));
         }
@@ -23,4 +29,10 @@ impl Benchmark {
             self.0.entry(*entity).unwrap().remove_component::<B>();
         }
     }
+
+    fn checksum(&mut self) -> f64 {
+        let a = <Read<A>>::query().iter(&self.0).count();
+        let b = <Read<B>>::query().iter(&self.0).count();
+        (a + b) as f64
+    }
 }
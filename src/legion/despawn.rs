@@ -0,0 +1,40 @@
+use legion::*;
+
+use crate::EcsBenchmark;
+
+struct A(f32);
+struct B(f32);
+
+pub struct Benchmark(World, Vec<Entity>);
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = World::default();
+
+        let entities = world
+            .extend((0..size).map(|_| (A(0.0), B(0.0))))
+            .to_vec();
+
+        Self(world, entities)
+    }
+
+    fn run(&mut self) {
+        let size = self.1.len();
+        for entity in self.1.drain(..) {
+            self.0.remove(entity);
+        }
+
+        self.1
+            .extend_from_slice(self.0.extend((0..size).map(|_| (A(0.0), B(0.0)))));
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let a = <Read<A>>::query().iter(&self.0).count();
+        let b = <Read<B>>::query().iter(&self.0).count();
+        (a + b) as f64
+    }
+}
@@ -0,0 +1,56 @@
+use cgmath::*;
+use legion::*;
+
+use crate::EcsBenchmark;
+
+#[derive(Copy, Clone)]
+struct Position(Vector3<f32>);
+
+#[derive(Copy, Clone)]
+struct Velocity(Vector3<f32>);
+
+#[derive(Copy, Clone)]
+struct Drag(f32);
+
+#[derive(Copy, Clone)]
+struct Frozen;
+
+type Moving = (Write<Position>, Read<Velocity>, TryRead<Drag>);
+
+pub struct Benchmark(World);
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = World::default();
+        for i in 0..size {
+            let entity = world.push((Position(Vector3::unit_x()), Velocity(Vector3::unit_x())));
+            let mut entry = world.entry(entity).unwrap();
+            if i % 3 == 0 {
+                entry.add_component(Drag(0.5));
+            }
+            if i % 4 == 0 {
+                entry.add_component(Frozen);
+            }
+        }
+
+        Self(world)
+    }
+
+    fn run(&mut self) {
+        let mut query = Moving::query().filter(!component::<Frozen>());
+        query.for_each_mut(&mut self.0, |(position, velocity, drag)| {
+            position.0 += velocity.0 * (1.0 - drag.map_or(0.0, |drag| drag.0));
+        });
+    }
+
+    fn checksum(&mut self) -> f64 {
+        <Read<Position>>::query()
+            .iter(&self.0)
+            .map(|position| position.0.x as f64)
+            .sum()
+    }
+}
@@ -2,11 +2,13 @@ use legion::*;
 use query::Query;
 use storage::PackOptions;
 
+use crate::EcsBenchmark;
+
 macro_rules! create_entities {
-    ($world:ident; $( $variants:ident ),*) => {
+    ($world:ident, $size:expr; $( $variants:ident ),*) => {
         $(
             struct $variants(f32);
-            $world.extend((0..20).map(|_| ($variants(0.0), Data(1.0))));
+            $world.extend((0..$size).map(|_| ($variants(0.0), Data(1.0))));
         )*
     };
 }
@@ -15,11 +17,15 @@ struct Data(f32);
 
 pub struct Benchmark(World, Query<Write<Data>>);
 
-impl Benchmark {
-    pub fn new() -> Self {
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
         let mut world = World::default();
 
-        create_entities!(world; A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z);
+        create_entities!(world, size; A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z);
         world.pack(PackOptions::force());
 
         let query = Write::<Data>::query();
@@ -27,9 +33,16 @@ impl Benchmark {
         Self(world, query)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         self.1.for_each_mut(&mut self.0, |data| {
             data.0 *= 2.0;
         });
     }
+
+    fn checksum(&mut self) -> f64 {
+        <Read<Data>>::query()
+            .iter(&self.0)
+            .map(|data| data.0 as f64)
+            .sum()
+    }
 }
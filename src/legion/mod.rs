@@ -1,8 +1,13 @@
 pub mod add_remove;
+pub mod despawn;
+pub mod filtered_iter;
 pub mod frag_iter;
 pub mod heavy_compute;
+pub mod random_access;
 pub mod schedule;
 pub mod serialize_binary;
 pub mod serialize_text;
 pub mod simple_insert;
 pub mod simple_iter;
+
+pub const NAME: &str = "legion";
@@ -1,6 +1,8 @@
 use legion::*;
 use storage::PackOptions;
 
+use crate::EcsBenchmark;
+
 struct A(f32);
 struct B(f32);
 struct C(f32);
@@ -24,17 +26,21 @@ fn ce(c: &mut C, e: &mut E) {
 
 pub struct Benchmark(World, Resources, Schedule);
 
-impl Benchmark {
-    pub fn new() -> Self {
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
         let mut world = World::default();
 
-        world.extend((0..10000).map(|_| (A(0.0), B(0.0))));
+        world.extend((0..size).map(|_| (A(1.0), B(2.0))));
 
-        world.extend((0..10000).map(|_| (A(0.0), B(0.0), C(0.0))));
+        world.extend((0..size).map(|_| (A(1.0), B(2.0), C(3.0))));
 
-        world.extend((0..10000).map(|_| (A(0.0), B(0.0), C(0.0), D(0.0))));
+        world.extend((0..size).map(|_| (A(1.0), B(2.0), C(3.0), D(4.0))));
 
-        world.extend((0..10000).map(|_| (A(0.0), B(0.0), C(0.0), E(0.0))));
+        world.extend((0..size).map(|_| (A(1.0), B(2.0), C(3.0), E(5.0))));
 
         world.pack(PackOptions::force());
 
@@ -47,7 +53,15 @@ impl Benchmark {
         Self(world, Resources::default(), schedule)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         self.2.execute(&mut self.0, &mut self.1);
     }
+
+    fn checksum(&mut self) -> f64 {
+        let b: f32 = <Read<B>>::query().iter(&self.0).map(|b| b.0).sum();
+        let c: f32 = <Read<C>>::query().iter(&self.0).map(|c| c.0).sum();
+        let d: f32 = <Read<D>>::query().iter(&self.0).map(|d| d.0).sum();
+        let e: f32 = <Read<E>>::query().iter(&self.0).map(|e| e.0).sum();
+        (b + c + d + e) as f64
+    }
 }
@@ -1,6 +1,8 @@
 use legion::*;
 use serde::{de::DeserializeSeed, Deserialize, Serialize};
 
+use crate::EcsBenchmark;
+
 #[derive(Default, Copy, Clone, Serialize, Deserialize)]
 struct Transform([f32; 16]);
 
@@ -25,18 +27,22 @@ struct Velocity {
     z: f32,
 }
 
-pub struct Benchmark(World, Registry<u8>);
+pub struct Benchmark(World, Registry<u8>, Option<World>);
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
 
-impl Benchmark {
-    pub fn new() -> Self {
+    fn setup(size: usize) -> Self {
         let mut world = World::default();
 
         world.extend(
             (
-                vec![Transform::default(); 1000],
-                vec![Position::default(); 1000],
-                vec![Rotation::default(); 1000],
-                vec![Velocity::default(); 1000],
+                vec![Transform::default(); size],
+                (0..size).map(|i| Position { x: i as f32, y: 0.0, z: 0.0 }).collect::<Vec<_>>(),
+                vec![Rotation::default(); size],
+                vec![Velocity { x: 1.0, y: 0.0, z: 0.0 }; size],
             )
                 .into_soa(),
         );
@@ -47,19 +53,34 @@ impl Benchmark {
         registry.register::<Rotation>(2);
         registry.register::<Velocity>(3);
 
-        Self(world, registry)
+        Self(world, registry, None)
     }
 
-    pub fn run(&mut self) {
-        let Self(world, registry) = self;
+    fn run(&mut self) {
+        let Self(world, registry, deserialized) = self;
         let serializable = &world.as_serializable(any(), &*registry);
 
         let serialized = ron::ser::to_string(serializable).unwrap();
 
         let mut deserializer = ron::de::Deserializer::from_str(&serialized).unwrap();
-        registry
-            .as_deserialize()
-            .deserialize(&mut deserializer)
-            .unwrap();
+        *deserialized = Some(
+            registry
+                .as_deserialize()
+                .deserialize(&mut deserializer)
+                .unwrap(),
+        );
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let world = self.2.as_ref().unwrap();
+        <(Read<Position>, Read<Velocity>)>::query()
+            .iter(world)
+            .map(|(pos, vel)| (pos.x + vel.x) as f64)
+            .sum()
+    }
+
+    fn teardown(&mut self) {
+        self.2 = None;
     }
 }
+
@@ -1,6 +1,8 @@
 use cgmath::*;
 use legion::*;
 
+use crate::EcsBenchmark;
+
 #[derive(Copy, Clone)]
 struct Transform(Matrix4<f32>);
 
@@ -13,24 +15,42 @@ struct Rotation(Vector3<f32>);
 #[derive(Copy, Clone)]
 struct Velocity(Vector3<f32>);
 
-pub struct Benchmark;
+pub struct Benchmark(usize, Option<World>);
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
 
-impl Benchmark {
-    pub fn new() -> Self {
-        Self
+    fn setup(size: usize) -> Self {
+        Self(size, None)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         let mut world = World::default();
 
         world.extend(
             (
-                vec![Transform(Matrix4::from_scale(1.0)); 1_000_000],
-                vec![Position(Vector3::unit_x()); 1_000_000],
-                vec![Rotation(Vector3::unit_x()); 1_000_000],
-                vec![Velocity(Vector3::unit_x()); 1_000_000],
+                vec![Transform(Matrix4::from_scale(1.0)); self.0],
+                vec![Position(Vector3::unit_x()); self.0],
+                vec![Rotation(Vector3::unit_x()); self.0],
+                vec![Velocity(Vector3::unit_x()); self.0],
             )
                 .into_soa(),
         );
+
+        self.1 = Some(world);
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let world = self.1.as_ref().unwrap();
+        <Read<Position>>::query()
+            .iter(world)
+            .map(|position| position.0.x as f64)
+            .sum()
+    }
+
+    fn teardown(&mut self) {
+        self.1 = None;
     }
 }
@@ -3,6 +3,8 @@ use legion::*;
 use query::Query;
 use storage::PackOptions;
 
+use crate::EcsBenchmark;
+
 #[derive(Copy, Clone)]
 struct Transform(Matrix4<f32>);
 
@@ -17,16 +19,20 @@ struct Velocity(Vector3<f32>);
 
 pub struct Benchmark(World, Query<(Read<Velocity>, Write<Position>)>);
 
-impl Benchmark {
-    pub fn new() -> Self {
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
         let mut world = World::default();
 
         world.extend(
             (
-                vec![Transform(Matrix4::from_scale(1.0)); 1_000_000],
-                vec![Position(Vector3::unit_x()); 1_000_000],
-                vec![Rotation(Vector3::unit_x()); 1_000_000],
-                vec![Velocity(Vector3::unit_x()); 1_000_000],
+                vec![Transform(Matrix4::from_scale(1.0)); size],
+                vec![Position(Vector3::unit_x()); size],
+                vec![Rotation(Vector3::unit_x()); size],
+                vec![Velocity(Vector3::unit_x()); size],
             )
                 .into_soa(),
         );
@@ -37,9 +43,16 @@ impl Benchmark {
         Self(world, query)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         self.1.for_each_mut(&mut self.0, |(velocity, position)| {
             position.0 += velocity.0;
         });
     }
+
+    fn checksum(&mut self) -> f64 {
+        <Read<Position>>::query()
+            .iter(&self.0)
+            .map(|position| position.0.x as f64)
+            .sum()
+    }
 }
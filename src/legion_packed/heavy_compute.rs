@@ -3,6 +3,8 @@ use legion::*;
 use query::Query;
 use storage::PackOptions;
 
+use crate::EcsBenchmark;
+
 #[derive(Copy, Clone)]
 struct Position(Vector3<f32>);
 
@@ -14,15 +16,19 @@ struct Velocity(Vector3<f32>);
 
 pub struct Benchmark(World, Query<(Write<Position>, Write<Matrix4<f32>>)>);
 
-impl Benchmark {
-    pub fn new() -> Self {
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
         let options = WorldOptions {
             groups: vec![<(Position, Matrix4<f32>)>::to_group()],
         };
 
         let mut world = World::new(options);
 
-        world.extend((0..1000).map(|_| {
+        world.extend((0..size).map(|_| {
             (
                 Matrix4::<f32>::from_angle_x(Rad(1.2)),
                 Position(Vector3::unit_x()),
@@ -37,7 +43,7 @@ impl Benchmark {
         Self(world, query)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         self.1.par_for_each_mut(&mut self.0, |(pos, mat)| {
             for _ in 0..100 {
                 *mat = mat.invert().unwrap();
@@ -45,4 +51,11 @@ impl Benchmark {
             pos.0 = mat.transform_vector(pos.0);
         });
     }
+
+    fn checksum(&mut self) -> f64 {
+        <Read<Position>>::query()
+            .iter(&self.0)
+            .map(|position| (position.0.x + position.0.y + position.0.z) as f64)
+            .sum()
+    }
 }
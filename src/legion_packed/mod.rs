@@ -1,3 +1,5 @@
 pub mod heavy_compute;
 pub mod schedule;
 pub mod simple_iter;
+
+pub const NAME: &str = "legion (packed)";
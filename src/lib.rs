@@ -1,5 +1,8 @@
 #![allow(clippy::new_without_default)]
 
+pub mod harness;
+pub mod scenarios;
+
 pub mod bevy;
 pub mod hecs;
 pub mod legion;
@@ -9,4 +12,6 @@ pub mod shipyard;
 pub mod specs;
 pub mod raw;
 pub mod local;
-pub mod tribles;
\ No newline at end of file
+pub mod tribles;
+
+pub use harness::{EcsBenchmark, Scenario};
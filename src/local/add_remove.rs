@@ -0,0 +1,64 @@
+use crate::EcsBenchmark;
+use super::{Column, ColumnReadGuard, ColumnWriteGuard, Id, World};
+
+pub struct A(f32);
+pub struct B(f32);
+
+pub struct Benchmark {
+    world: Box<World>,
+    a: Column,
+    b: Column,
+    entities: Vec<Id>,
+}
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = Box::new(World::new());
+
+        let a = world.register::<A>().unwrap();
+        let b = world.register::<B>().unwrap();
+
+        let entities = {
+            let mut a = a.write().unwrap();
+            (0..size)
+                .map(|_| {
+                    let mut entity = world.new_entity();
+                    entity.add_component(&mut a, A(0.0));
+                    entity.id
+                })
+                .collect()
+        };
+        // A cached query, which gains and loses a match with every `B`.
+        world.query::<(&A, &B)>().unwrap();
+
+        Benchmark {
+            world,
+            a,
+            b,
+            entities,
+        }
+    }
+
+    fn run(&mut self) {
+        let mut b: ColumnWriteGuard<'_, B> = self.b.write().unwrap();
+
+        for entity in &self.entities {
+            let mut entity = self.world.entity_mut(entity).unwrap();
+            entity.add_component(&mut b, B(0.0));
+        }
+
+        for entity in &self.entities {
+            self.world.remove_component(entity, &mut b).unwrap();
+        }
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let a: ColumnReadGuard<'_, A> = self.a.read().unwrap();
+        let b: ColumnReadGuard<'_, B> = self.b.read().unwrap();
+        (a.len() + b.len()) as f64
+    }
+}
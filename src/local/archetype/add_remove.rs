@@ -0,0 +1,42 @@
+use crate::EcsBenchmark;
+use super::{Id, World};
+
+pub struct A(f32);
+pub struct B(f32);
+
+pub struct Benchmark {
+    world: Box<World>,
+    entities: Vec<Id>,
+}
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = Box::new(World::new());
+
+        let entities = (0..size)
+            .map(|_| world.spawn((A(0.0),)).unwrap())
+            .collect();
+
+        Benchmark { world, entities }
+    }
+
+    fn run(&mut self) {
+        for entity in &self.entities {
+            self.world.entity_mut(entity).unwrap().insert(B(0.0));
+        }
+
+        for entity in &self.entities {
+            self.world.entity_mut(entity).unwrap().remove::<B>().unwrap();
+        }
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let a: usize = self.world.query::<(&A,)>().unwrap().map(|(a,)| a.len()).sum();
+        let b: usize = self.world.query::<(&B,)>().unwrap().map(|(b,)| b.len()).sum();
+        (a + b) as f64
+    }
+}
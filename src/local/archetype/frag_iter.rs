@@ -0,0 +1,48 @@
+use crate::EcsBenchmark;
+use super::World;
+
+macro_rules! create_entities {
+    ($world:ident, $size:expr; $( $variants:ident ),*) => {
+        $(
+            struct $variants(f32);
+            for _ in 0..$size {
+                $world.spawn(($variants(0.0), Data(1.0))).unwrap();
+            }
+        )*
+    };
+}
+
+struct Data(f32);
+
+pub struct Benchmark {
+    world: Box<World>
+}
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = Box::new(World::new());
+        create_entities!(world, size; A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z);
+
+        Benchmark { world }
+    }
+
+    fn run(&mut self) {
+        for (mut data,) in self.world.query::<(&mut Data,)>().unwrap() {
+            for data in data.iter_mut() {
+                data.0 *= 2.0;
+            }
+        }
+    }
+
+    fn checksum(&mut self) -> f64 {
+        self.world
+            .query::<(&Data,)>()
+            .unwrap()
+            .map(|(data,)| data.iter().map(|data| data.0 as f64).sum::<f64>())
+            .sum()
+    }
+}
@@ -0,0 +1,410 @@
+//! Archetype storage for `local`: entities with the same set of components share a table
+//! with one dense column per component, without per-row entity ids.
+//!
+//! This is an alternative to the id-tagged columns of [`super::World`], so that both
+//! layouts can be compared on the same scenarios.
+
+use std::any::{type_name, Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use smallvec::SmallVec;
+use tribles::fucid;
+
+use super::{ComponentError, Id, COMPONENTS_INLINE};
+
+pub mod add_remove;
+pub mod frag_iter;
+pub mod simple_iter;
+
+pub const NAME: &str = "local (archetype)";
+
+/// The component types of an archetype, sorted.
+pub type Components = SmallVec<[TypeId; COMPONENTS_INLINE]>;
+
+/// A type-erased `RwLock<Vec<T>>`, one column of an archetype.
+pub trait ErasedColumn: Send + Sync {
+    fn as_any(&self) -> &(dyn Any + Send + Sync);
+
+    fn as_any_mut(&mut self) -> &mut (dyn Any + Send + Sync);
+
+    /// An empty column of the same component type.
+    fn empty(&self) -> Box<dyn ErasedColumn>;
+
+    /// Swap-removes the row and pushes it onto `to`, which has to store the same type.
+    fn move_row(&mut self, row: usize, to: &mut dyn ErasedColumn);
+}
+
+impl<T: Send + Sync + 'static> ErasedColumn for RwLock<Vec<T>> {
+    fn as_any(&self) -> &(dyn Any + Send + Sync) {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut (dyn Any + Send + Sync) {
+        self
+    }
+
+    fn empty(&self) -> Box<dyn ErasedColumn> {
+        Box::new(RwLock::new(Vec::<T>::new()))
+    }
+
+    fn move_row(&mut self, row: usize, to: &mut dyn ErasedColumn) {
+        let value = rows(self).swap_remove(row);
+        let to = to.as_any_mut().downcast_mut::<Self>().expect("columns of the same component");
+        rows(to).push(value);
+    }
+}
+
+fn new_column<T: Send + Sync + 'static>() -> Box<dyn ErasedColumn> {
+    Box::new(RwLock::new(Vec::<T>::new()))
+}
+
+fn rows<T>(column: &mut RwLock<Vec<T>>) -> &mut Vec<T> {
+    column.get_mut().expect("column lock poisoned")
+}
+
+/// The entities sharing a set of components, with a column per component.
+pub struct Archetype {
+    components: Components,
+    /// In the order of `components`.
+    columns: Vec<Box<dyn ErasedColumn>>,
+    entities: Vec<Id>,
+}
+
+impl Archetype {
+    fn position(&self, component: &TypeId) -> Option<usize> {
+        self.components.binary_search(component).ok()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    pub fn entities(&self) -> &[Id] {
+        &self.entities
+    }
+
+    fn column<T: Send + Sync + 'static>(&self) -> Option<&RwLock<Vec<T>>> {
+        let position = self.position(&TypeId::of::<T>())?;
+        self.columns[position].as_any().downcast_ref()
+    }
+
+    fn column_mut<T: Send + Sync + 'static>(&mut self) -> Option<&mut Vec<T>> {
+        let position = self.position(&TypeId::of::<T>())?;
+        self.columns[position].as_any_mut().downcast_mut().map(rows)
+    }
+}
+
+/// Components spawned together, a tuple of up to 16 component types.
+pub trait Bundle {
+    /// The component types, sorted, or an error if one occurs twice.
+    fn components() -> Result<Components, ComponentError>;
+
+    /// An empty column for each component, in the order of [`Bundle::components`].
+    fn columns() -> Vec<Box<dyn ErasedColumn>>;
+
+    fn push(self, archetype: &mut Archetype);
+}
+
+/// Shared or exclusive access to a component's column in each matching archetype.
+pub trait Fetch {
+    type Chunk<'a>;
+
+    fn component() -> (TypeId, &'static str);
+
+    fn lock(archetype: &Archetype) -> Self::Chunk<'_>;
+}
+
+impl<T: Send + Sync + 'static> Fetch for &T {
+    type Chunk<'a> = RwLockReadGuard<'a, Vec<T>>;
+
+    fn component() -> (TypeId, &'static str) {
+        (TypeId::of::<T>(), type_name::<T>())
+    }
+
+    fn lock(archetype: &Archetype) -> Self::Chunk<'_> {
+        let column = archetype.column::<T>().expect("query matched the archetype");
+        column.read().expect("column lock poisoned")
+    }
+}
+
+impl<T: Send + Sync + 'static> Fetch for &mut T {
+    type Chunk<'a> = RwLockWriteGuard<'a, Vec<T>>;
+
+    fn component() -> (TypeId, &'static str) {
+        (TypeId::of::<T>(), type_name::<T>())
+    }
+
+    fn lock(archetype: &Archetype) -> Self::Chunk<'_> {
+        let column = archetype.column::<T>().expect("query matched the archetype");
+        column.write().expect("column lock poisoned")
+    }
+}
+
+/// A tuple of [`Fetch`]es, e.g. `(&mut Position, &Velocity)`, see [`World::query`].
+pub trait QueryData {
+    type Chunk<'a>;
+
+    /// The component types in tuple order, or an error if one occurs twice.
+    fn components() -> Result<Components, ComponentError>;
+
+    fn lock(archetype: &Archetype) -> Self::Chunk<'_>;
+}
+
+/// Checks `components` for duplicates, then sorts them.
+fn unaliased(components: &[(TypeId, &'static str)], sorted: bool) -> Result<Components, ComponentError> {
+    for (index, (component, name)) in components.iter().enumerate() {
+        if components[..index].iter().any(|(other, _)| other == component) {
+            return Err(ComponentError::Aliased { component: name });
+        }
+    }
+    let mut components: Components = components.iter().map(|(component, _)| *component).collect();
+    if sorted {
+        components.sort_unstable();
+    }
+    Ok(components)
+}
+
+macro_rules! impl_tuples {
+    ($( $t:ident $index:tt ),*) => {
+        impl<$( $t: Send + Sync + 'static ),*> Bundle for ($( $t, )*) {
+            fn components() -> Result<Components, ComponentError> {
+                unaliased(&[$( (TypeId::of::<$t>(), type_name::<$t>()), )*], true)
+            }
+
+            fn columns() -> Vec<Box<dyn ErasedColumn>> {
+                let mut columns = vec![$( (TypeId::of::<$t>(), new_column::<$t>()), )*];
+                columns.sort_unstable_by_key(|(component, _)| *component);
+                columns.into_iter().map(|(_, column)| column).collect()
+            }
+
+            fn push(self, archetype: &mut Archetype) {
+                $( archetype.column_mut::<$t>().expect("bundle matches the archetype").push(self.$index); )*
+            }
+        }
+
+        impl<$( $t: Fetch ),*> QueryData for ($( $t, )*) {
+            type Chunk<'a> = ($( $t::Chunk<'a>, )*);
+
+            fn components() -> Result<Components, ComponentError> {
+                unaliased(&[$( $t::component(), )*], false)
+            }
+
+            fn lock(archetype: &Archetype) -> Self::Chunk<'_> {
+                ($( $t::lock(archetype), )*)
+            }
+        }
+    };
+}
+
+impl_tuples!(A 0);
+impl_tuples!(A 0, B 1);
+impl_tuples!(A 0, B 1, C 2);
+impl_tuples!(A 0, B 1, C 2, D 3);
+impl_tuples!(A 0, B 1, C 2, D 3, E 4);
+impl_tuples!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_tuples!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_tuples!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_tuples!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_tuples!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+impl_tuples!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+impl_tuples!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+impl_tuples!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12);
+impl_tuples!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13);
+impl_tuples!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13, O 14);
+impl_tuples!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13, O 14, P 15);
+
+/// A transition between archetypes, adding a component with a constructor for its column,
+/// or removing one.
+#[derive(Clone, Copy)]
+enum Edge {
+    Add(TypeId, fn() -> Box<dyn ErasedColumn>),
+    Remove(TypeId),
+}
+
+#[derive(Clone, Copy)]
+struct Location {
+    archetype: usize,
+    row: usize,
+}
+
+pub struct World {
+    archetypes: Vec<Archetype>,
+    by_components: HashMap<Components, usize>,
+    locations: HashMap<Id, Location>,
+}
+
+impl World {
+    pub fn new() -> Self {
+        Self {
+            archetypes: Vec::new(),
+            by_components: HashMap::new(),
+            locations: HashMap::new(),
+        }
+    }
+
+    pub fn archetypes(&self) -> &[Archetype] {
+        &self.archetypes
+    }
+
+    fn push_archetype(&mut self, components: Components, columns: Vec<Box<dyn ErasedColumn>>) -> usize {
+        let index = self.archetypes.len();
+        self.by_components.insert(components.clone(), index);
+        self.archetypes.push(Archetype {
+            components,
+            columns,
+            entities: Vec::new(),
+        });
+        index
+    }
+
+    /// Creates an entity with the components of the bundle.
+    pub fn spawn<B: Bundle>(&mut self, bundle: B) -> Result<Id, ComponentError> {
+        let components = B::components()?;
+        let archetype = match self.by_components.get(&components) {
+            Some(&archetype) => archetype,
+            None => self.push_archetype(components, B::columns()),
+        };
+
+        let id = fucid();
+        let table = &mut self.archetypes[archetype];
+        bundle.push(table);
+        table.entities.push(id);
+        let row = table.entities.len() - 1;
+        self.locations.insert(id, Location { archetype, row });
+        Ok(id)
+    }
+
+    pub fn entity_mut(&mut self, id: &Id) -> Option<EntityMut<'_>> {
+        if !self.locations.contains_key(id) {
+            return None;
+        }
+        Some(EntityMut { world: self, id: *id })
+    }
+
+    /// Iterates the archetypes having all components of `Q`, yielding the locked columns
+    /// of each, e.g. `(RwLockWriteGuard<Vec<Position>>, RwLockReadGuard<Vec<Velocity>>)`
+    /// for `(&mut Position, &Velocity)`.
+    ///
+    /// The world is borrowed exclusively, so that the chunks of two queries, which keep
+    /// their columns locked, can't be alive at the same time and block each other.
+    pub fn query<Q: QueryData>(&mut self) -> Result<impl Iterator<Item = Q::Chunk<'_>>, ComponentError> {
+        let components = Q::components()?;
+        Ok(self
+            .archetypes
+            .iter()
+            .filter(move |archetype| {
+                !archetype.is_empty() && components.iter().all(|component| archetype.position(component).is_some())
+            })
+            .map(|archetype| Q::lock(archetype)))
+    }
+
+    /// The archetype for the components of `from` with one component added or removed.
+    fn neighbour(&mut self, from: usize, edge: Edge) -> usize {
+        let mut components = self.archetypes[from].components.clone();
+        match edge {
+            Edge::Add(component, _) => {
+                let position = components.binary_search(&component).unwrap_err();
+                components.insert(position, component);
+            }
+            Edge::Remove(component) => {
+                let position = components.binary_search(&component).expect("removed component is present");
+                components.remove(position);
+            }
+        }
+        if let Some(&to) = self.by_components.get(&components) {
+            return to;
+        }
+
+        let source = &self.archetypes[from];
+        let mut columns: Vec<(TypeId, Box<dyn ErasedColumn>)> = source
+            .components
+            .iter()
+            .zip(&source.columns)
+            .filter(|(component, _)| !matches!(edge, Edge::Remove(removed) if removed == **component))
+            .map(|(component, column)| (*component, column.empty()))
+            .collect();
+        if let Edge::Add(component, column) = edge {
+            columns.push((component, column()));
+        }
+        columns.sort_unstable_by_key(|(component, _)| *component);
+        self.push_archetype(components, columns.into_iter().map(|(_, column)| column).collect())
+    }
+
+    /// Moves the entity at `row` of `from` into `to`, returning the row it ends up in.
+    ///
+    /// Components `to` doesn't have must already have been taken out of their columns.
+    fn move_entity(&mut self, from: usize, row: usize, to: usize) -> usize {
+        let (source, target) = pair_mut(&mut self.archetypes, from, to);
+        for (component, column) in source.components.iter().zip(source.columns.iter_mut()) {
+            if let Some(position) = target.position(component) {
+                column.move_row(row, &mut *target.columns[position]);
+            }
+        }
+
+        let entity = source.entities.swap_remove(row);
+        if let Some(moved) = source.entities.get(row) {
+            self.locations.get_mut(moved).expect("archetype entities exist").row = row;
+        }
+        target.entities.push(entity);
+        let row = target.entities.len() - 1;
+        self.locations.insert(entity, Location { archetype: to, row });
+        row
+    }
+}
+
+/// Mutable access to an entity, adding or removing components moves it to another archetype.
+pub struct EntityMut<'a> {
+    world: &'a mut World,
+    id: Id,
+}
+
+impl<'a> EntityMut<'a> {
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    fn location(&self) -> Location {
+        self.world.locations[&self.id]
+    }
+
+    /// Adds the component to the entity, replacing and returning the previous value if the
+    /// entity already had one.
+    pub fn insert<T: Send + Sync + 'static>(&mut self, component: T) -> Option<T> {
+        let Location { archetype: from, row } = self.location();
+        if let Some(column) = self.world.archetypes[from].column_mut::<T>() {
+            return Some(std::mem::replace(&mut column[row], component));
+        }
+
+        let to = self.world.neighbour(from, Edge::Add(TypeId::of::<T>(), new_column::<T>));
+        self.world.move_entity(from, row, to);
+        let target = self.world.archetypes[to].column_mut::<T>().expect("archetype was made for T");
+        target.push(component);
+        None
+    }
+
+    /// Removes the component from the entity, returning it if the entity had one.
+    pub fn remove<T: Send + Sync + 'static>(&mut self) -> Option<T> {
+        let Location { archetype: from, row } = self.location();
+        let component = self.world.archetypes[from].column_mut::<T>()?.swap_remove(row);
+
+        let to = self.world.neighbour(from, Edge::Remove(TypeId::of::<T>()));
+        self.world.move_entity(from, row, to);
+        Some(component)
+    }
+}
+
+fn pair_mut<T>(slice: &mut [T], a: usize, b: usize) -> (&mut T, &mut T) {
+    assert_ne!(a, b, "can't borrow the same element twice");
+    if a < b {
+        let (head, tail) = slice.split_at_mut(b);
+        (&mut head[a], &mut tail[0])
+    } else {
+        let (head, tail) = slice.split_at_mut(a);
+        (&mut tail[0], &mut head[b])
+    }
+}
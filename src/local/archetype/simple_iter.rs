@@ -0,0 +1,58 @@
+use cgmath::*;
+
+use crate::EcsBenchmark;
+use super::World;
+
+#[derive(Copy, Clone)]
+pub struct Transform(Matrix4<f32>);
+#[derive(Copy, Clone)]
+pub struct Position(Vector3<f32>);
+
+#[derive(Copy, Clone)]
+pub struct Rotation(Vector3<f32>);
+
+#[derive(Copy, Clone)]
+pub struct Velocity(Vector3<f32>);
+
+pub struct Benchmark {
+    world: Box<World>
+}
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = Box::new(World::new());
+
+        for _ in 0..size {
+            world
+                .spawn((
+                    Transform(Matrix4::<f32>::from_scale(1.0)),
+                    Position(Vector3::unit_x()),
+                    Rotation(Vector3::unit_x()),
+                    Velocity(Vector3::unit_x()),
+                ))
+                .unwrap();
+        }
+
+        Benchmark { world }
+    }
+
+    fn run(&mut self) {
+        for (mut pos, vel) in self.world.query::<(&mut Position, &Velocity)>().unwrap() {
+            for (pos, vel) in pos.iter_mut().zip(vel.iter()) {
+                pos.0 += vel.0;
+            }
+        }
+    }
+
+    fn checksum(&mut self) -> f64 {
+        self.world
+            .query::<(&Position,)>()
+            .unwrap()
+            .map(|(pos,)| pos.iter().map(|pos| pos.0.x as f64).sum::<f64>())
+            .sum()
+    }
+}
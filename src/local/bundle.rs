@@ -0,0 +1,105 @@
+use smallvec::{smallvec, SmallVec};
+
+use super::{Column, ColumnWriteGuard, ComponentError, Id, World, COMPONENTS_INLINE};
+
+/// A tuple of components spawned together as one entity, e.g. `(Position, Velocity)`, see
+/// [`World::spawn_batch`].
+pub trait Bundle: Sized {
+    /// The write guards of the bundle's columns, in tuple order.
+    type Guards<'c>;
+    /// The components of many bundles as one vector per type, e.g.
+    /// `(Vec<Position>, Vec<Velocity>)`, see [`World::spawn_soa`].
+    type Soa;
+
+    /// The columns of the types in tuple order, registering the types that aren't yet, see
+    /// [`World::register`].
+    fn columns(world: &mut World) -> Result<SmallVec<[Column; COMPONENTS_INLINE]>, ComponentError>;
+
+    /// Write locks the columns returned by [`Bundle::columns`], which must be distinct.
+    fn lock(columns: &[Column]) -> Result<Self::Guards<'_>, ComponentError>;
+
+    /// The row count and last entity of each column.
+    fn ends(guards: &Self::Guards<'_>) -> SmallVec<[(usize, Option<Id>); COMPONENTS_INLINE]>;
+
+    fn reserve(guards: &mut Self::Guards<'_>, additional: usize);
+
+    /// Appends a row for the entity to each column.
+    fn push(self, guards: &mut Self::Guards<'_>, entity: Id);
+
+    /// The number of bundles, or `None` if the vectors differ in length.
+    fn soa_len(soa: &Self::Soa) -> Option<usize>;
+
+    /// Appends the rows of the entities to each column, one column after the other.
+    fn extend(soa: Self::Soa, guards: &mut Self::Guards<'_>, entities: &[Id]);
+}
+
+/// The column registered for `T`, registering it if needed.
+fn column_or_register<T: Send + Sync + 'static>(world: &mut World) -> Result<Column, ComponentError> {
+    match world.column::<T>() {
+        Ok(column) => Ok(column.clone()),
+        Err(_) => world.register::<T>(),
+    }
+}
+
+fn end<T>(guard: &ColumnWriteGuard<'_, T>) -> (usize, Option<Id>) {
+    (guard.len(), guard.last().map(|row| row.entity))
+}
+
+macro_rules! impl_bundle {
+    ($( $component:ident $index:tt ),*) => {
+        impl<$( $component: Send + Sync + 'static ),*> Bundle for ($( $component, )*) {
+            type Guards<'c> = ($( ColumnWriteGuard<'c, $component>, )*);
+            type Soa = ($( Vec<$component>, )*);
+
+            fn columns(world: &mut World) -> Result<SmallVec<[Column; COMPONENTS_INLINE]>, ComponentError> {
+                Ok(smallvec![$( column_or_register::<$component>(world)? ),*])
+            }
+
+            fn lock(columns: &[Column]) -> Result<Self::Guards<'_>, ComponentError> {
+                Ok(($( columns[$index].write::<$component>()?, )*))
+            }
+
+            fn ends(guards: &Self::Guards<'_>) -> SmallVec<[(usize, Option<Id>); COMPONENTS_INLINE]> {
+                smallvec![$( end(&guards.$index) ),*]
+            }
+
+            fn reserve(guards: &mut Self::Guards<'_>, additional: usize) {
+                $( guards.$index.reserve(additional); )*
+            }
+
+            fn push(self, guards: &mut Self::Guards<'_>, entity: Id) {
+                $( guards.$index.push_row(entity, self.$index); )*
+            }
+
+            fn soa_len(soa: &Self::Soa) -> Option<usize> {
+                let lens = [$( soa.$index.len() ),*];
+                Some(lens[0]).filter(|len| lens.iter().all(|other| other == len))
+            }
+
+            fn extend(soa: Self::Soa, guards: &mut Self::Guards<'_>, entities: &[Id]) {
+                $(
+                    for (entity, component) in entities.iter().zip(soa.$index) {
+                        guards.$index.push_row(*entity, component);
+                    }
+                )*
+            }
+        }
+    };
+}
+
+impl_bundle!(A 0);
+impl_bundle!(A 0, B 1);
+impl_bundle!(A 0, B 1, C 2);
+impl_bundle!(A 0, B 1, C 2, D 3);
+impl_bundle!(A 0, B 1, C 2, D 3, E 4);
+impl_bundle!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_bundle!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_bundle!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_bundle!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_bundle!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+impl_bundle!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+impl_bundle!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+impl_bundle!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12);
+impl_bundle!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13);
+impl_bundle!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13, O 14);
+impl_bundle!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13, O 14, P 15);
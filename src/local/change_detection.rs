@@ -0,0 +1,185 @@
+use cgmath::*;
+
+use crate::EcsBenchmark;
+use super::{Changed, ColumnReadGuard, ColumnWriteGuard, QueryKey, Term, World};
+
+#[derive(Copy, Clone)]
+pub struct Transform(Matrix4<f32>);
+
+#[derive(Copy, Clone)]
+pub struct Position(Vector3<f32>);
+
+#[derive(Copy, Clone)]
+pub struct Rotation(Vector3<f32>);
+
+#[derive(Copy, Clone)]
+pub struct Velocity(Vector3<f32>);
+
+/// Every run moves 1% of the entities, by a different stride offset each time.
+const STRIDE: usize = 100;
+
+/// Recomputes the transforms of all entities after moving some of them, going through
+/// the typed query which marks every fetched `Transform` as changed.
+pub struct Benchmark {
+    world: Box<World>,
+    runs: usize,
+}
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = populate(size);
+
+        // Builds the cached query outside of the timed section.
+        world.query::<(&mut Transform, &Position)>().unwrap();
+
+        Benchmark {
+            world,
+            runs: 0,
+        }
+    }
+
+    fn run(&mut self) {
+        move_some(&self.world, self.runs);
+        self.runs += 1;
+
+        let mut query = self.world.query::<(&mut Transform, &Position)>().unwrap();
+        while let Some((tf, pos)) = query.next_match() {
+            tf.0 = Matrix4::from_translation(pos.0);
+        }
+    }
+
+    fn checksum(&mut self) -> f64 {
+        checksum(&self.world)
+    }
+}
+
+/// The same as [`Benchmark`], but only recomputing the transforms of the entities that
+/// moved, through a [`Changed`] filter.
+pub struct ChangedBenchmark {
+    world: Box<World>,
+    runs: usize,
+}
+
+impl EcsBenchmark for ChangedBenchmark {
+    fn name() -> &'static str {
+        super::CHANGED_NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = populate(size);
+
+        // Builds the cached query and sees the freshly added positions outside of the
+        // timed section.
+        let mut query = world.query::<(&mut Transform, Changed<Position>)>().unwrap();
+        while query.next_match().is_some() {}
+        drop(query);
+
+        ChangedBenchmark {
+            world,
+            runs: 0,
+        }
+    }
+
+    fn run(&mut self) {
+        move_some(&self.world, self.runs);
+        self.runs += 1;
+
+        let mut query = self.world.query::<(&mut Transform, Changed<Position>)>().unwrap();
+        while let Some((tf, pos)) = query.next_match() {
+            tf.0 = Matrix4::from_translation(pos.0);
+        }
+    }
+
+    fn checksum(&mut self) -> f64 {
+        checksum(&self.world)
+    }
+}
+
+/// The same as [`Benchmark`], but indexing the columns by hand through the cached row
+/// indices, stamping the rows through [`ColumnWriteGuard::modify`] instead of a typed
+/// query.
+pub struct UncheckedBenchmark {
+    world: Box<World>,
+    query: QueryKey,
+    runs: usize,
+}
+
+impl EcsBenchmark for UncheckedBenchmark {
+    fn name() -> &'static str {
+        super::UNCHECKED_NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = populate(size);
+
+        let mut query = QueryKey::new();
+        query.push(Term::required(world.component_id::<Transform>().unwrap()));
+        query.push(Term::required(world.component_id::<Position>().unwrap()));
+
+        world.new_query(&query);
+
+        UncheckedBenchmark {
+            world,
+            query,
+            runs: 0,
+        }
+    }
+
+    fn run(&mut self) {
+        move_some(&self.world, self.runs);
+        self.runs += 1;
+
+        let mut tf: ColumnWriteGuard<'_, Transform> = self.world.column::<Transform>().unwrap().write().unwrap();
+        let pos: ColumnReadGuard<'_, Position> = self.world.column::<Position>().unwrap().read().unwrap();
+
+        for q in self.world.query_indices(&self.query).unwrap() {
+            tf.modify(q[0]).0 = Matrix4::from_translation(pos[q[1]].inner.0);
+        }
+    }
+
+    fn checksum(&mut self) -> f64 {
+        checksum(&self.world)
+    }
+}
+
+fn populate(size: usize) -> Box<World> {
+    let mut world = Box::new(World::new());
+
+    {
+        let tf = world.register::<Transform>().unwrap();
+        let mut tf = tf.write().unwrap();
+        let pos = world.register::<Position>().unwrap();
+        let mut pos = pos.write().unwrap();
+        let rot = world.register::<Rotation>().unwrap();
+        let mut rot = rot.write().unwrap();
+        let vel = world.register::<Velocity>().unwrap();
+        let mut vel = vel.write().unwrap();
+
+        for _ in 0..size {
+            let mut entity = world.new_entity();
+            entity.add_component(&mut tf, Transform(Matrix4::from_translation(Vector3::unit_x())));
+            entity.add_component(&mut pos, Position(Vector3::unit_x()));
+            entity.add_component(&mut rot, Rotation(Vector3::unit_x()));
+            entity.add_component(&mut vel, Velocity(Vector3::unit_x()));
+        }
+    }
+
+    world
+}
+
+/// Moves every [`STRIDE`]th entity, marking its position as changed.
+fn move_some(world: &World, runs: usize) {
+    let mut pos: ColumnWriteGuard<'_, Position> = world.column::<Position>().unwrap().write().unwrap();
+    for index in (runs % STRIDE..pos.len()).step_by(STRIDE) {
+        pos.modify(index).0 += Vector3::unit_x();
+    }
+}
+
+fn checksum(world: &World) -> f64 {
+    let tf: ColumnReadGuard<'_, Transform> = world.column::<Transform>().unwrap().read().unwrap();
+    tf.iter().map(|row| row.inner.0.w.x as f64).sum()
+}
@@ -0,0 +1,317 @@
+use std::any::{Any, TypeId};
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+
+use super::{ComponentError, Entity, EntityMut, Id, World};
+
+/// An entity recorded with [`Commands::spawn`], which only gets its id from the world's
+/// allocator once the buffer is applied, see [`Commands::apply`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Spawned(usize);
+
+/// The entity a command applies to, either an existing one or one spawned by the same
+/// buffer.
+#[derive(Copy, Clone)]
+pub enum Target {
+    Id(Id),
+    Spawned(Spawned),
+}
+
+impl From<Id> for Target {
+    fn from(id: Id) -> Self {
+        Target::Id(id)
+    }
+}
+
+impl From<Spawned> for Target {
+    fn from(spawned: Spawned) -> Self {
+        Target::Spawned(spawned)
+    }
+}
+
+impl Target {
+    /// The id of the entity, given the ids of the buffer's spawned entities.
+    fn id(self, spawned: &[Id]) -> Id {
+        match self {
+            Target::Id(id) => id,
+            Target::Spawned(Spawned(index)) => spawned[index],
+        }
+    }
+
+    /// Whether the entity exists once the buffer's entities are spawned.
+    fn exists(self, world: &World) -> Result<(), ComponentError> {
+        match self {
+            Target::Id(id) if world.slot(&id).is_none() => Err(ComponentError::NoEntity { id }),
+            _ => Ok(()),
+        }
+    }
+
+    /// The same entity after the buffer was appended to one that spawns `spawns` entities.
+    fn shift(self, spawns: usize) -> Self {
+        match self {
+            Target::Spawned(Spawned(index)) => Target::Spawned(Spawned(index + spawns)),
+            target => target,
+        }
+    }
+}
+
+/// The inserts or removes recorded for the column of one type, which are applied under a
+/// single lock of it.
+trait Batch: Send {
+    fn len(&self) -> usize;
+
+    /// Checks that the batch can be applied, before anything of the buffer is.
+    fn check(&self, world: &World) -> Result<(), ComponentError>;
+
+    fn apply(self: Box<Self>, world: &mut World, spawned: &[Id]) -> Result<(), ComponentError>;
+
+    /// Moves the commands of `other`, a batch of the same type, behind the ones of this
+    /// batch.
+    fn append(&mut self, other: Box<dyn Batch>);
+
+    /// Refers to the buffer's spawned entities as if it was appended to one spawning
+    /// `spawns` entities.
+    fn shift(&mut self, spawns: usize);
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+/// Components to be added to their entities, registering their type if needed, see
+/// [`World::register`].
+struct Inserts<T> {
+    rows: Vec<(Target, T)>,
+}
+
+impl<T: Send + Sync + 'static> Batch for Inserts<T> {
+    fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn check(&self, world: &World) -> Result<(), ComponentError> {
+        self.rows.iter().try_for_each(|(target, _)| target.exists(world))
+    }
+
+    fn apply(self: Box<Self>, world: &mut World, spawned: &[Id]) -> Result<(), ComponentError> {
+        let column = match world.column::<T>() {
+            Ok(column) => column.clone(),
+            Err(_) => world.register::<T>()?,
+        };
+        let mut column = column.write::<T>()?;
+        column.reserve(self.rows.len());
+        for (target, component) in self.rows {
+            let slot = world.slot(&target.id(spawned)).expect("entities are checked before applying");
+            EntityMut { world, slot }.add_component(&mut column, component);
+        }
+        Ok(())
+    }
+
+    fn append(&mut self, other: Box<dyn Batch>) {
+        let mut other = other.into_any().downcast::<Self>().expect("batches are appended by type");
+        self.rows.append(&mut other.rows);
+    }
+
+    fn shift(&mut self, spawns: usize) {
+        for (target, _) in &mut self.rows {
+            *target = target.shift(spawns);
+        }
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Components of type `T` to be removed from their entities.
+struct Removes<T> {
+    targets: Vec<Target>,
+    component: PhantomData<fn() -> T>,
+}
+
+impl<T: Send + Sync + 'static> Batch for Removes<T> {
+    fn len(&self) -> usize {
+        self.targets.len()
+    }
+
+    fn check(&self, world: &World) -> Result<(), ComponentError> {
+        world.column::<T>()?;
+        self.targets.iter().try_for_each(|target| target.exists(world))
+    }
+
+    fn apply(self: Box<Self>, world: &mut World, spawned: &[Id]) -> Result<(), ComponentError> {
+        let column = world.column::<T>()?.clone();
+        let mut column = column.write::<T>()?;
+        for target in self.targets {
+            world.remove_component(&target.id(spawned), &mut column);
+        }
+        Ok(())
+    }
+
+    fn append(&mut self, other: Box<dyn Batch>) {
+        let mut other = other.into_any().downcast::<Self>().expect("batches are appended by type");
+        self.targets.append(&mut other.targets);
+    }
+
+    fn shift(&mut self, spawns: usize) {
+        for target in &mut self.targets {
+            *target = target.shift(spawns);
+        }
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Batches keyed by their type, in the order the types were first recorded.
+#[derive(Default)]
+struct Batches {
+    types: HashMap<TypeId, usize>,
+    batches: Vec<Box<dyn Batch>>,
+}
+
+impl Batches {
+    fn len(&self) -> usize {
+        self.batches.iter().map(|batch| batch.len()).sum()
+    }
+
+    /// The batch of type `B`, which is created with `new` if there is none yet.
+    fn get_or_insert_with<B: Batch + 'static>(&mut self, new: impl FnOnce() -> B) -> &mut B {
+        let batches = &mut self.batches;
+        let index = *self.types.entry(TypeId::of::<B>()).or_insert_with(|| {
+            batches.push(Box::new(new()));
+            batches.len() - 1
+        });
+        self.batches[index].as_any_mut().downcast_mut().expect("batches are indexed by type")
+    }
+
+    /// Moves the batches of `other`, which was recorded next to a buffer spawning `spawns`
+    /// entities, behind the ones of this one.
+    fn append(&mut self, other: &mut Batches, spawns: usize) {
+        let mut types: Vec<(TypeId, usize)> = other.types.drain().collect();
+        types.sort_unstable_by_key(|&(_, index)| index);
+        for ((ty, _), mut batch) in types.into_iter().zip(other.batches.drain(..)) {
+            batch.shift(spawns);
+            match self.types.get(&ty) {
+                Some(&index) => self.batches[index].append(batch),
+                None => {
+                    self.types.insert(ty, self.batches.len());
+                    self.batches.push(batch);
+                }
+            }
+        }
+    }
+
+    /// Applies the batches one after the other, stopping at the first failing one.
+    fn apply(self, world: &mut World, spawned: &[Id]) -> Result<(), ComponentError> {
+        self.batches.into_iter().try_for_each(|batch| batch.apply(world, spawned))
+    }
+
+    fn check(&self, world: &World) -> Result<(), ComponentError> {
+        self.batches.iter().try_for_each(|batch| batch.check(world))
+    }
+}
+
+/// Structural changes recorded while the world is borrowed, to be applied once it can be
+/// borrowed exclusively, see [`Commands::apply`].
+///
+/// Buffers can be sent between threads, so every thread can record into its own and have
+/// them [`append`](Commands::append)ed before applying.
+#[derive(Default)]
+pub struct Commands {
+    /// Number of entities recorded with [`Commands::spawn`].
+    spawns: usize,
+    inserts: Batches,
+    removes: Batches,
+    despawns: HashSet<Id>,
+}
+
+impl Commands {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.spawns + self.inserts.len() + self.removes.len() + self.despawns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Moves the commands of `other` behind the ones of this buffer.
+    ///
+    /// The entities spawned by `other` come after the ones of this buffer, in the ids
+    /// returned by [`Commands::apply`].
+    pub fn append(&mut self, other: &mut Commands) {
+        self.inserts.append(&mut other.inserts, self.spawns);
+        self.removes.append(&mut other.removes, self.spawns);
+        self.despawns.extend(other.despawns.drain());
+        self.spawns += std::mem::take(&mut other.spawns);
+    }
+
+    /// Records the creation of an entity, which gets its id from the world's allocator
+    /// once the buffer is applied.
+    pub fn spawn(&mut self) -> Spawned {
+        self.spawns += 1;
+        Spawned(self.spawns - 1)
+    }
+
+    pub fn despawn(&mut self, id: Id) {
+        self.despawns.insert(id);
+    }
+
+    /// Records adding the component to the entity, registering its type if needed, see
+    /// [`World::register`].
+    pub fn insert<T: Send + Sync + 'static>(&mut self, entity: impl Into<Target>, component: T) {
+        let inserts = self.inserts.get_or_insert_with(|| Inserts::<T> { rows: Vec::new() });
+        inserts.rows.push((entity.into(), component));
+    }
+
+    pub fn remove<T: Send + Sync + 'static>(&mut self, entity: impl Into<Target>) {
+        let removes = self.removes.get_or_insert_with(|| Removes::<T> {
+            targets: Vec::new(),
+            component: PhantomData,
+        });
+        removes.targets.push(entity.into());
+    }
+
+    /// Applies and clears the recorded commands, returning the ids of the spawned entities
+    /// in the order they were recorded.
+    ///
+    /// The commands take effect in phases rather than in the order they were recorded:
+    /// first the spawns, whose ids are allocated in bulk, then the inserts and then the
+    /// removes, each grouped by column and applied under a single lock of it, and finally
+    /// the despawns, see [`World::despawn_batch`]. A component removed before it is
+    /// inserted, or an entity despawned before a component is inserted into it, therefore
+    /// ends up the same as if the commands were recorded the other way around.
+    ///
+    /// Every entity and, for the removes, column is checked to exist before anything is
+    /// applied, so that a failing buffer leaves the world untouched, unless a column turns
+    /// out to be poisoned or its type's id taken halfway through. The despawns are only
+    /// applied if everything else was. The buffer is cleared either way. None of the columns
+    /// may be borrowed while applying.
+    pub fn apply(&mut self, world: &mut World) -> Result<Vec<Id>, ComponentError> {
+        let Commands { spawns, inserts, removes, despawns } = std::mem::take(self);
+        inserts.check(world)?;
+        removes.check(world)?;
+
+        let start = world.entities.len();
+        let spawned = world.claim_ids(start, spawns);
+        world.entities.extend(spawned.iter().map(|id| Entity::new(*id)));
+
+        inserts.apply(world, &spawned)?;
+        removes.apply(world, &spawned)?;
+        world.despawn_batch(&despawns);
+        Ok(spawned)
+    }
+}
@@ -0,0 +1,140 @@
+use crate::EcsBenchmark;
+use super::{Column, ColumnReadGuard, Commands, Id, World};
+
+pub struct A(f32);
+pub struct B(f32);
+
+/// Despawns every other entity and spawns as many new ones, recorded into a [`Commands`]
+/// buffer and applied in one batch.
+pub struct Benchmark {
+    world: Box<World>,
+    a: Column,
+    b: Column,
+    commands: Commands,
+    entities: Vec<Id>,
+}
+
+impl Benchmark {
+    fn spawn(&mut self, size: usize) {
+        for _ in 0..size {
+            let entity = self.commands.spawn();
+            self.commands.insert(entity, A(0.0));
+            self.commands.insert(entity, B(0.0));
+        }
+    }
+
+    /// Applies the recorded commands, keeping track of the spawned entities.
+    fn apply(&mut self) {
+        let spawned = self.commands.apply(&mut self.world).unwrap();
+        self.entities.extend(spawned);
+    }
+}
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::BATCHED_NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = Box::new(World::new());
+
+        let a = world.register::<A>().unwrap();
+        let b = world.register::<B>().unwrap();
+
+        let mut bench = Benchmark {
+            world,
+            a,
+            b,
+            commands: Commands::new(),
+            entities: Vec::with_capacity(size),
+        };
+        bench.spawn(size);
+        bench.apply();
+        // A cached query, which the batch has to keep up to date.
+        bench.world.query::<(&A, &B)>().unwrap();
+        bench
+    }
+
+    fn run(&mut self) {
+        let doomed = split_doomed(&mut self.entities);
+        for entity in &doomed {
+            self.commands.despawn(*entity);
+        }
+
+        self.spawn(doomed.len());
+        self.apply();
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let a: ColumnReadGuard<'_, A> = self.a.read().unwrap();
+        let b: ColumnReadGuard<'_, B> = self.b.read().unwrap();
+        (a.len() + b.len()) as f64
+    }
+}
+
+/// The same work as [`Benchmark`], with every despawn and spawn applied immediately.
+pub struct ImmediateBenchmark {
+    world: Box<World>,
+    a: Column,
+    b: Column,
+    entities: Vec<Id>,
+}
+
+impl ImmediateBenchmark {
+    fn spawn(&mut self, size: usize) {
+        let mut a = self.a.write().unwrap();
+        let mut b = self.b.write().unwrap();
+
+        for _ in 0..size {
+            let mut entity = self.world.new_entity();
+            entity.add_component(&mut a, A(0.0));
+            entity.add_component(&mut b, B(0.0));
+            self.entities.push(entity.id);
+        }
+    }
+}
+
+impl EcsBenchmark for ImmediateBenchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = Box::new(World::new());
+
+        let a = world.register::<A>().unwrap();
+        let b = world.register::<B>().unwrap();
+
+        let mut bench = ImmediateBenchmark {
+            world,
+            a,
+            b,
+            entities: Vec::with_capacity(size),
+        };
+        bench.spawn(size);
+        bench.world.query::<(&A, &B)>().unwrap();
+        bench
+    }
+
+    fn run(&mut self) {
+        let doomed = split_doomed(&mut self.entities);
+        for entity in &doomed {
+            self.world.despawn(entity);
+        }
+
+        self.spawn(doomed.len());
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let a: ColumnReadGuard<'_, A> = self.a.read().unwrap();
+        let b: ColumnReadGuard<'_, B> = self.b.read().unwrap();
+        (a.len() + b.len()) as f64
+    }
+}
+
+/// Removes every other entity from `entities`, returning the removed ones.
+fn split_doomed(entities: &mut Vec<Id>) -> Vec<Id> {
+    let doomed = entities.iter().skip(1).step_by(2).copied().collect();
+    *entities = entities.iter().step_by(2).copied().collect();
+    doomed
+}
@@ -0,0 +1,124 @@
+use crate::EcsBenchmark;
+use super::{Column, ColumnReadGuard, Id, World};
+
+pub struct A(f32);
+pub struct B(f32);
+
+pub struct Benchmark {
+    world: Box<World>,
+    a: Column,
+    b: Column,
+    entities: Vec<Id>,
+}
+
+impl Benchmark {
+    fn spawn(&mut self, size: usize) {
+        let mut a = self.a.write().unwrap();
+        let mut b = self.b.write().unwrap();
+
+        for _ in 0..size {
+            let mut entity = self.world.new_entity();
+            entity.add_component(&mut a, A(0.0));
+            entity.add_component(&mut b, B(0.0));
+            self.entities.push(entity.id);
+        }
+    }
+}
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = Box::new(World::new());
+
+        let a = world.register::<A>().unwrap();
+        let b = world.register::<B>().unwrap();
+
+        let mut bench = Benchmark {
+            world,
+            a,
+            b,
+            entities: Vec::with_capacity(size),
+        };
+        bench.spawn(size);
+        // A cached query, which every despawn has to keep up to date.
+        bench.world.query::<(&A, &B)>().unwrap();
+        bench
+    }
+
+    fn run(&mut self) {
+        let size = self.entities.len();
+        for entity in self.entities.drain(..) {
+            self.world.despawn(&entity);
+        }
+
+        self.spawn(size);
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let a: ColumnReadGuard<'_, A> = self.a.read().unwrap();
+        let b: ColumnReadGuard<'_, B> = self.b.read().unwrap();
+        (a.len() + b.len()) as f64
+    }
+}
+
+/// The same work as [`Benchmark`], with the entities linked into a binary tree so that
+/// despawning its root takes all of them along.
+pub struct RecursiveBenchmark {
+    inner: Benchmark,
+}
+
+impl RecursiveBenchmark {
+    /// Spawns the entities the same way as [`Benchmark`], then links them into a binary
+    /// tree one by one, the `i`th entity becoming a child of the `(i - 1) / 2`th.
+    fn spawn(&mut self, size: usize) {
+        let start = self.inner.entities.len();
+        self.inner.spawn(size);
+
+        let (world, entities) = (&mut self.inner.world, &self.inner.entities);
+        for i in start.max(1)..entities.len() {
+            world.set_parent(&entities[i], &entities[(i - 1) / 2]).unwrap();
+        }
+    }
+}
+
+impl EcsBenchmark for RecursiveBenchmark {
+    fn name() -> &'static str {
+        super::RECURSIVE_NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = Box::new(World::new());
+
+        let a = world.register::<A>().unwrap();
+        let b = world.register::<B>().unwrap();
+
+        let mut bench = RecursiveBenchmark {
+            inner: Benchmark {
+                world,
+                a,
+                b,
+                entities: Vec::with_capacity(size),
+            },
+        };
+        bench.spawn(size);
+        bench.inner.world.query::<(&A, &B)>().unwrap();
+        bench
+    }
+
+    fn run(&mut self) {
+        let size = self.inner.entities.len();
+        if let Some(root) = self.inner.entities.first() {
+            self.inner.world.despawn_recursive(root);
+        }
+        self.inner.entities.clear();
+
+        self.spawn(size);
+    }
+
+    fn checksum(&mut self) -> f64 {
+        self.inner.checksum()
+    }
+}
@@ -0,0 +1,85 @@
+use std::marker::PhantomData;
+use std::slice;
+
+/// A double-buffered queue of events of type `T`, stored as a resource of the world, see
+/// [`World::add_events`](super::World::add_events).
+///
+/// Events sent since the last [`Events::update`] are kept for one more update, so a reader
+/// running once between updates sees every event, whether it runs before or after the
+/// writer. Systems send events through `&mut Events<T>` and read them through `&Events<T>`,
+/// so readers run in parallel with each other but not with writers.
+pub struct Events<T> {
+    /// Events sent before the last update.
+    previous: Vec<T>,
+    /// Events sent since the last update.
+    current: Vec<T>,
+    /// Number of events sent before the first one in `previous`.
+    start: usize,
+}
+
+impl<T> Default for Events<T> {
+    fn default() -> Self {
+        Self {
+            previous: Vec::new(),
+            current: Vec::new(),
+            start: 0,
+        }
+    }
+}
+
+impl<T> Events<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn send(&mut self, event: T) {
+        self.current.push(event);
+    }
+
+    /// Drops the events sent before the previous update and starts a new buffer, reusing
+    /// the allocation of the dropped one.
+    pub fn update(&mut self) {
+        self.start += self.previous.len();
+        std::mem::swap(&mut self.previous, &mut self.current);
+        self.current.clear();
+    }
+
+    /// Number of events sent so far, including dropped ones.
+    pub fn sent(&self) -> usize {
+        self.start + self.previous.len() + self.current.len()
+    }
+}
+
+/// The position of one reader in an [`Events`] queue, kept by the reading system.
+pub struct EventReader<T> {
+    /// Number of events sent before the next one to read.
+    cursor: usize,
+    events: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for EventReader<T> {
+    fn default() -> Self {
+        Self {
+            cursor: 0,
+            events: PhantomData,
+        }
+    }
+}
+
+impl<T> EventReader<T> {
+    /// A reader that starts with the events still buffered in the queue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The events sent since the previous read, in the order they were sent.
+    ///
+    /// Events dropped by [`Events::update`] before the reader got to them are skipped.
+    pub fn read<'e>(&mut self, events: &'e Events<T>) -> std::iter::Chain<slice::Iter<'e, T>, slice::Iter<'e, T>> {
+        let skipped = self.cursor.saturating_sub(events.start);
+        let previous = &events.previous[skipped.min(events.previous.len())..];
+        let current = &events.current[skipped.saturating_sub(events.previous.len())..];
+        self.cursor = events.sent();
+        previous.iter().chain(current.iter())
+    }
+}
@@ -0,0 +1,155 @@
+use crate::EcsBenchmark;
+use super::{Column, EventReader, Events, Query, QueryData, ResourceReadGuard, ResourceWriteGuard, Schedule, System, World};
+
+/// Index of the body, the entities touching in a [`Collision`] are neighbours.
+pub struct Body(u32);
+
+pub struct Health(f32);
+
+/// Number of collisions the body took part in.
+pub struct Contacts(u32);
+
+pub struct Collision {
+    a: u32,
+    b: u32,
+    impulse: f32,
+}
+
+fn impulse(body: u32) -> f32 {
+    1.0 + (body % 4) as f32
+}
+
+/// Sends a collision of every body with the next one.
+struct Collide {
+    bodies: u32,
+}
+
+impl System for Collide {
+    type Query = (&'static Body,);
+    type Resources = (&'static mut Events<Collision>,);
+
+    fn run(
+        &mut self,
+        mut query: Query<'_, <Self::Query as QueryData>::Columns<'_>>,
+        (mut events,): (ResourceWriteGuard<'_, Events<Collision>>,),
+    ) {
+        while let Some((body,)) = query.next_match() {
+            events.send(Collision {
+                a: body.0,
+                b: (body.0 + 1) % self.bodies,
+                impulse: impulse(body.0),
+            });
+        }
+    }
+}
+
+/// Subtracts the impulses of the collisions from the health of both bodies.
+struct Damage {
+    reader: EventReader<Collision>,
+    damage: Vec<f32>,
+}
+
+impl System for Damage {
+    type Query = (&'static mut Health, &'static Body);
+    type Resources = (&'static Events<Collision>,);
+
+    fn run(
+        &mut self,
+        mut query: Query<'_, <Self::Query as QueryData>::Columns<'_>>,
+        (events,): (ResourceReadGuard<'_, Events<Collision>>,),
+    ) {
+        for collision in self.reader.read(&events) {
+            self.damage[collision.a as usize] += collision.impulse;
+            self.damage[collision.b as usize] += collision.impulse;
+        }
+        while let Some((health, body)) = query.next_match() {
+            health.0 -= std::mem::take(&mut self.damage[body.0 as usize]);
+        }
+    }
+}
+
+/// Counts the collisions of every body.
+struct Tally {
+    reader: EventReader<Collision>,
+    contacts: Vec<u32>,
+}
+
+impl System for Tally {
+    type Query = (&'static mut Contacts, &'static Body);
+    type Resources = (&'static Events<Collision>,);
+
+    fn run(
+        &mut self,
+        mut query: Query<'_, <Self::Query as QueryData>::Columns<'_>>,
+        (events,): (ResourceReadGuard<'_, Events<Collision>>,),
+    ) {
+        for collision in self.reader.read(&events) {
+            self.contacts[collision.a as usize] += 1;
+            self.contacts[collision.b as usize] += 1;
+        }
+        while let Some((contacts, body)) = query.next_match() {
+            contacts.0 += std::mem::take(&mut self.contacts[body.0 as usize]);
+        }
+    }
+}
+
+pub struct Benchmark {
+    world: Box<World>,
+    schedule: Schedule,
+}
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = Box::new(World::new());
+
+        let body = world.register::<Body>().unwrap();
+        let health = world.register::<Health>().unwrap();
+        let contacts = world.register::<Contacts>().unwrap();
+        {
+            let mut body = body.write().unwrap();
+            let mut health = health.write().unwrap();
+            let mut contacts = contacts.write().unwrap();
+            for i in 0..size {
+                let mut entity = world.new_entity();
+                entity.add_component(&mut body, Body(i as u32));
+                entity.add_component(&mut health, Health(100.0));
+                entity.add_component(&mut contacts, Contacts(0));
+            }
+        }
+        world.add_events::<Collision>();
+
+        // `Collide` writes the queue that the other two read, so they run after it and in
+        // parallel with each other.
+        let mut schedule = Schedule::new();
+        schedule.add_system(&mut world, Collide { bodies: size as u32 }).unwrap();
+        schedule
+            .add_system(&mut world, Damage { reader: EventReader::new(), damage: vec![0.0; size] })
+            .unwrap();
+        schedule
+            .add_system(&mut world, Tally { reader: EventReader::new(), contacts: vec![0; size] })
+            .unwrap();
+
+        Benchmark { world, schedule }
+    }
+
+    fn run(&mut self) {
+        self.schedule.run(&self.world).unwrap();
+        self.world.update_events();
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let health = sum::<Health>(&self.world, |health| health.0 as f64);
+        let contacts = sum::<Contacts>(&self.world, |contacts| contacts.0 as f64);
+        health + contacts
+    }
+}
+
+fn sum<T: Send + Sync + 'static>(world: &World, value: impl Fn(&T) -> f64) -> f64 {
+    let column: &Column = world.column::<T>().unwrap();
+    let rows = column.read::<T>().unwrap();
+    rows.iter().map(|row| value(&row.inner)).sum()
+}
@@ -0,0 +1,147 @@
+use cgmath::*;
+
+use crate::EcsBenchmark;
+use super::{Sequential, Without, World};
+
+#[derive(Copy, Clone)]
+pub struct Position(Vector3<f32>);
+
+#[derive(Copy, Clone)]
+pub struct Velocity(Vector3<f32>);
+
+/// Fraction of the velocity that is lost, on every third entity.
+#[derive(Copy, Clone)]
+pub struct Drag(f32);
+
+/// Marks every fourth entity as not moving.
+#[derive(Copy, Clone)]
+pub struct Frozen;
+
+pub struct Benchmark {
+    world: Box<World>
+}
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = populate(World::new(), size);
+
+        // Builds the cached query outside of the timed section.
+        world.query::<(&mut Position, &Velocity, Option<&Drag>, Without<Frozen>)>().unwrap();
+
+        Benchmark {
+            world,
+        }
+    }
+
+    fn run(&mut self) {
+        let mut query = self.world.query::<(&mut Position, &Velocity, Option<&Drag>, Without<Frozen>)>().unwrap();
+        while let Some((pos, vel, drag, ())) = query.next_match() {
+            pos.0 += vel.0 * (1.0 - drag.map_or(0.0, |drag| drag.0));
+        }
+    }
+
+    fn checksum(&mut self) -> f64 {
+        checksum(&self.world)
+    }
+}
+
+/// The same iteration as [`Benchmark`] as a merge join, which looks up the entities of
+/// the required columns in the `Drag` and `Frozen` columns.
+pub struct JoinBenchmark {
+    world: Box<World>
+}
+
+impl EcsBenchmark for JoinBenchmark {
+    fn name() -> &'static str {
+        super::JOIN_NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = populate(World::new(), size);
+        world.sort_columns();
+
+        JoinBenchmark {
+            world,
+        }
+    }
+
+    fn run(&mut self) {
+        let mut joined = self.world.join::<(&mut Position, &Velocity, Option<&Drag>, Without<Frozen>)>().unwrap();
+        while let Some((pos, vel, drag, ())) = joined.next_match() {
+            pos.0 += vel.0 * (1.0 - drag.map_or(0.0, |drag| drag.0));
+        }
+    }
+
+    fn checksum(&mut self) -> f64 {
+        checksum(&self.world)
+    }
+}
+
+/// The same iteration as [`JoinBenchmark`], over entities with sequential ids, whose
+/// columns are sorted from the start.
+pub struct SequentialJoinBenchmark {
+    world: Box<World>
+}
+
+impl EcsBenchmark for SequentialJoinBenchmark {
+    fn name() -> &'static str {
+        super::SEQUENTIAL_JOIN_NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let world = populate(World::with_ids(Sequential::new()), size);
+        assert!(world.unsorted.is_empty(), "sequential ids should keep the columns sorted");
+
+        SequentialJoinBenchmark {
+            world,
+        }
+    }
+
+    fn run(&mut self) {
+        let mut joined = self.world.join::<(&mut Position, &Velocity, Option<&Drag>, Without<Frozen>)>().unwrap();
+        while let Some((pos, vel, drag, ())) = joined.next_match() {
+            pos.0 += vel.0 * (1.0 - drag.map_or(0.0, |drag| drag.0));
+        }
+    }
+
+    fn checksum(&mut self) -> f64 {
+        checksum(&self.world)
+    }
+}
+
+fn populate(world: World, size: usize) -> Box<World> {
+    let mut world = Box::new(world);
+
+    let pos = world.register::<Position>().unwrap();
+    let mut pos = pos.write().unwrap();
+    let vel = world.register::<Velocity>().unwrap();
+    let mut vel = vel.write().unwrap();
+    let drag = world.register::<Drag>().unwrap();
+    let mut drag = drag.write().unwrap();
+    let frozen = world.register::<Frozen>().unwrap();
+    let mut frozen = frozen.write().unwrap();
+
+    for i in 0..size {
+        let mut entity = world.new_entity();
+        entity.add_component(&mut pos, Position(Vector3::unit_x()));
+        entity.add_component(&mut vel, Velocity(Vector3::unit_x()));
+        if i % 3 == 0 {
+            entity.add_component(&mut drag, Drag(0.5));
+        }
+        if i % 4 == 0 {
+            entity.add_component(&mut frozen, Frozen);
+        }
+    }
+
+    world
+}
+
+fn checksum(world: &World) -> f64 {
+    let pos = world.column::<Position>().unwrap();
+    let pos = pos.read::<Position>().unwrap();
+    pos.iter().map(|row| row.inner.0.x as f64).sum()
+}
@@ -0,0 +1,99 @@
+use crate::EcsBenchmark;
+use super::World;
+
+macro_rules! create_entities {
+    ($world:ident, $data:ident, $size:expr; $( $variants:ident ),*) => {
+        $(
+            struct $variants(f32);
+            let variant = $world.register::<$variants>().unwrap();
+            let mut variant = variant.write().unwrap();
+            for _ in 0..$size {
+                let mut entity = $world.new_entity();
+                entity.add_component(&mut variant, $variants(0.0));
+                entity.add_component(&mut $data, Data(1.0));
+            }
+        )*
+    };
+}
+
+struct Data(f32);
+
+pub struct Benchmark {
+    world: Box<World>,
+}
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = populate(size);
+
+        // Builds the cached query outside of the timed section.
+        world.query::<(&mut Data,)>().unwrap();
+
+        Benchmark { world }
+    }
+
+    fn run(&mut self) {
+        let mut query = self.world.query::<(&mut Data,)>().unwrap();
+        while let Some((data,)) = query.next_match() {
+            data.0 *= 2.0;
+        }
+    }
+
+    fn checksum(&mut self) -> f64 {
+        checksum(&mut self.world)
+    }
+}
+
+/// The same iteration as [`Benchmark`], but as a merge join over the sorted column
+/// instead of going through the cached query.
+pub struct JoinBenchmark {
+    world: Box<World>,
+}
+
+impl EcsBenchmark for JoinBenchmark {
+    fn name() -> &'static str {
+        super::JOIN_NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = populate(size);
+        world.sort_columns();
+
+        JoinBenchmark { world }
+    }
+
+    fn run(&mut self) {
+        let mut joined = self.world.join::<(&mut Data,)>().unwrap();
+        while let Some((data,)) = joined.next_match() {
+            data.0 *= 2.0;
+        }
+    }
+
+    fn checksum(&mut self) -> f64 {
+        checksum(&mut self.world)
+    }
+}
+
+fn populate(size: usize) -> Box<World> {
+    let mut world = Box::new(World::new());
+
+    let data = world.register::<Data>().unwrap();
+    let mut data = data.write().unwrap();
+    create_entities!(world, data, size; A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z);
+    drop(data);
+
+    world
+}
+
+fn checksum(world: &mut World) -> f64 {
+    let mut query = world.query::<(&Data,)>().unwrap();
+    let mut sum = 0.0;
+    while let Some((data,)) = query.next_match() {
+        sum += data.0 as f64;
+    }
+    sum
+}
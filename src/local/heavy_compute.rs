@@ -0,0 +1,69 @@
+use cgmath::*;
+
+use crate::EcsBenchmark;
+use super::{ColumnReadGuard, World};
+
+#[derive(Copy, Clone)]
+pub struct Position(Vector3<f32>);
+
+#[derive(Copy, Clone)]
+pub struct Rotation(Vector3<f32>);
+
+#[derive(Copy, Clone)]
+pub struct Velocity(Vector3<f32>);
+
+pub struct Benchmark {
+    world: Box<World>
+}
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = Box::new(World::new());
+
+        {
+            let mat = world.register::<Matrix4<f32>>().unwrap();
+            let mut mat = mat.write().unwrap();
+            let pos = world.register::<Position>().unwrap();
+            let mut pos = pos.write().unwrap();
+            let rot = world.register::<Rotation>().unwrap();
+            let mut rot = rot.write().unwrap();
+            let vel = world.register::<Velocity>().unwrap();
+            let mut vel = vel.write().unwrap();
+
+            for _ in 0..size {
+                let mut entity = world.new_entity();
+                entity.add_component(&mut mat, Matrix4::<f32>::from_angle_x(Rad(1.2)));
+                entity.add_component(&mut pos, Position(Vector3::unit_x()));
+                entity.add_component(&mut rot, Rotation(Vector3::unit_x()));
+                entity.add_component(&mut vel, Velocity(Vector3::unit_x()));
+            }
+        }
+
+        // Builds the cached query outside of the timed section.
+        world.par_query::<(&mut Position, &mut Matrix4<f32>)>().unwrap();
+
+        Benchmark {
+            world,
+        }
+    }
+
+    fn run(&mut self) {
+        let mut query = self.world.par_query::<(&mut Position, &mut Matrix4<f32>)>().unwrap();
+        query.for_each(|(pos, mat)| {
+            for _ in 0..100 {
+                *mat = mat.invert().unwrap();
+            }
+
+            pos.0 = mat.transform_vector(pos.0);
+        });
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let pos: ColumnReadGuard<'_, Position> = self.world.column::<Position>().unwrap().read().unwrap();
+        pos.iter().map(|row| (row.inner.0.x + row.inner.0.y + row.inner.0.z) as f64).sum()
+    }
+}
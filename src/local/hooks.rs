@@ -0,0 +1,73 @@
+use super::Id;
+
+type Hook<T> = Box<dyn Fn(&Id, &T) + Send + Sync>;
+type ReplaceHook<T> = Box<dyn Fn(&Id, &T, &T) + Send + Sync>;
+
+/// Callbacks run while components of type `T` are added to, replaced in or removed from
+/// their column, see [`World::new_component_with_hooks`](super::World::new_component_with_hooks).
+///
+/// Hooks run synchronously with the column write locked, so they must not lock it again.
+/// State they maintain, like a spatial index, has to be shared through a lock or atomics.
+pub struct Hooks<T> {
+    on_add: Option<Hook<T>>,
+    on_replace: Option<ReplaceHook<T>>,
+    on_remove: Option<Hook<T>>,
+}
+
+impl<T> Default for Hooks<T> {
+    fn default() -> Self {
+        Self {
+            on_add: None,
+            on_replace: None,
+            on_remove: None,
+        }
+    }
+}
+
+impl<T> Hooks<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs with the entity and its component after a component is added.
+    pub fn on_add(mut self, hook: impl Fn(&Id, &T) + Send + Sync + 'static) -> Self {
+        self.on_add = Some(Box::new(hook));
+        self
+    }
+
+    /// Runs with the entity, its previous and its new component after a component is
+    /// replaced by adding another one to the same entity.
+    pub fn on_replace(mut self, hook: impl Fn(&Id, &T, &T) + Send + Sync + 'static) -> Self {
+        self.on_replace = Some(Box::new(hook));
+        self
+    }
+
+    /// Runs with the entity and its component before a component is dropped, when it is
+    /// removed from the entity or the entity is despawned.
+    pub fn on_remove(mut self, hook: impl Fn(&Id, &T) + Send + Sync + 'static) -> Self {
+        self.on_remove = Some(Box::new(hook));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.on_add.is_none() && self.on_replace.is_none() && self.on_remove.is_none()
+    }
+
+    pub(super) fn added(&self, entity: &Id, component: &T) {
+        if let Some(hook) = &self.on_add {
+            hook(entity, component);
+        }
+    }
+
+    pub(super) fn replaced(&self, entity: &Id, previous: &T, component: &T) {
+        if let Some(hook) = &self.on_replace {
+            hook(entity, previous, component);
+        }
+    }
+
+    pub(super) fn removed(&self, entity: &Id, component: &T) {
+        if let Some(hook) = &self.on_remove {
+            hook(entity, component);
+        }
+    }
+}
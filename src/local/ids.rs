@@ -0,0 +1,121 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use tribles::fucid;
+
+use super::Id;
+
+/// Hands out the ids of the entities created by a world, see
+/// [`World::with_ids`](super::World::with_ids).
+///
+/// An allocator must never return the same id twice. Ids that are taken anyway, e.g. by
+/// entities spawned under a given id or loaded from a save, are skipped by the world.
+pub trait IdAllocator: Send + Sync {
+    fn next(&mut self) -> Id;
+
+    /// Appends `count` fresh ids to `ids`.
+    fn allocate(&mut self, count: usize, ids: &mut Vec<Id>) {
+        ids.reserve(count);
+        ids.extend((0..count).map(|_| self.next()));
+    }
+}
+
+/// Ids from [`fucid`], which are unique across worlds and processes but in no particular
+/// order. The default of every world.
+#[derive(Default)]
+pub struct Fucid;
+
+impl IdAllocator for Fucid {
+    fn next(&mut self) -> Id {
+        fucid()
+    }
+}
+
+/// Ids counting up from zero, stored big endian so that they also sort in the order they
+/// were handed out. Columns filled with them stay sorted by entity.
+#[derive(Default)]
+pub struct Sequential {
+    next: u128,
+}
+
+impl Sequential {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl IdAllocator for Sequential {
+    fn next(&mut self) -> Id {
+        let id = self.next;
+        self.next += 1;
+        id.to_be_bytes()
+    }
+
+    fn allocate(&mut self, count: usize, ids: &mut Vec<Id>) {
+        let start = self.next;
+        self.next += count as u128;
+        ids.extend((start..self.next).map(u128::to_be_bytes));
+    }
+}
+
+/// Sequential ids from ranges reserved in bulk from a counter shared with other
+/// allocators, e.g. of worlds filled on different threads, which touch the counter once
+/// per range.
+///
+/// Ids are ascending within a range, and so are the ranges of one allocator.
+pub struct Ranges {
+    counter: Arc<AtomicU64>,
+    range: u64,
+    next: u64,
+    end: u64,
+}
+
+impl Ranges {
+    /// An allocator with a counter of its own, reserving `range` ids at a time.
+    pub fn new(range: u64) -> Self {
+        assert!(range > 0, "ranges can't be empty");
+        Ranges {
+            counter: Arc::new(AtomicU64::new(0)),
+            range,
+            next: 0,
+            end: 0,
+        }
+    }
+
+    /// Another allocator reserving its ranges from the same counter.
+    pub fn share(&self) -> Self {
+        Ranges {
+            counter: self.counter.clone(),
+            range: self.range,
+            next: 0,
+            end: 0,
+        }
+    }
+
+    /// Reserves a range of at least `count` ids, dropping what is left of the current one.
+    fn refill(&mut self, count: u64) {
+        let len = count.max(self.range);
+        self.next = self.counter.fetch_add(len, Ordering::Relaxed);
+        self.end = self.next + len;
+    }
+}
+
+impl IdAllocator for Ranges {
+    fn next(&mut self) -> Id {
+        if self.next == self.end {
+            self.refill(1);
+        }
+        let id = self.next;
+        self.next += 1;
+        (id as u128).to_be_bytes()
+    }
+
+    fn allocate(&mut self, count: usize, ids: &mut Vec<Id>) {
+        if self.end - self.next < count as u64 {
+            self.refill(count as u64);
+        }
+        let start = self.next;
+        self.next += count as u64;
+        ids.extend((start..self.next).map(|id| (id as u128).to_be_bytes()));
+    }
+}
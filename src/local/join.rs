@@ -0,0 +1,338 @@
+use smallvec::{smallvec, SmallVec};
+
+use super::{ColumnReadGuard, ColumnWriteGuard, Id, Presence, Row, MISSING, QUERY_INLINE};
+
+/// A column taking part in a [`join`], either shared or exclusive access to its rows.
+///
+/// Rows have to be sorted by entity, see [`World::sort_columns`](super::World::sort_columns).
+pub trait JoinColumn {
+    type Item<'a>
+    where
+        Self: 'a;
+
+    /// Whether entities have to, may or must not have a row in the column.
+    const PRESENCE: Presence = Presence::Required;
+
+    fn rows(&self) -> usize;
+
+    fn entity(&self, index: usize) -> &Id;
+
+    /// Index of the first row at or after `from` whose entity isn't less than `entity`.
+    fn seek(&self, from: usize, entity: &Id) -> usize;
+
+    /// Whether the row passes the column's filter, see [`ChangeFilter`](super::ChangeFilter).
+    fn matches(&self, _index: usize) -> bool {
+        true
+    }
+
+    fn fetch(&mut self, index: usize) -> Self::Item<'_>;
+}
+
+impl<T> JoinColumn for &[Row<T>] {
+    type Item<'a> = &'a T where Self: 'a;
+
+    fn rows(&self) -> usize {
+        self.len()
+    }
+
+    fn entity(&self, index: usize) -> &Id {
+        &self[index].entity
+    }
+
+    fn seek(&self, from: usize, entity: &Id) -> usize {
+        self.len() - gallop(&self[from..], |row| row.entity < *entity).len()
+    }
+
+    fn fetch(&mut self, index: usize) -> Self::Item<'_> {
+        &self[index].inner
+    }
+}
+
+impl<T> JoinColumn for &mut [Row<T>] {
+    type Item<'a> = &'a mut T where Self: 'a;
+
+    fn rows(&self) -> usize {
+        self.len()
+    }
+
+    fn entity(&self, index: usize) -> &Id {
+        &self[index].entity
+    }
+
+    fn seek(&self, from: usize, entity: &Id) -> usize {
+        self.len() - gallop(&self[from..], |row| row.entity < *entity).len()
+    }
+
+    fn fetch(&mut self, index: usize) -> Self::Item<'_> {
+        &mut self[index].inner
+    }
+}
+
+impl<T> JoinColumn for ColumnReadGuard<'_, T> {
+    type Item<'a> = &'a T where Self: 'a;
+
+    fn rows(&self) -> usize {
+        self.len()
+    }
+
+    fn entity(&self, index: usize) -> &Id {
+        &self[index].entity
+    }
+
+    fn seek(&self, from: usize, entity: &Id) -> usize {
+        (&self[..]).seek(from, entity)
+    }
+
+    fn fetch(&mut self, index: usize) -> Self::Item<'_> {
+        &self[index].inner
+    }
+}
+
+/// Fetching marks the row as changed, see [`ColumnWriteGuard::modify`].
+impl<T> JoinColumn for ColumnWriteGuard<'_, T> {
+    type Item<'a> = &'a mut T where Self: 'a;
+
+    fn rows(&self) -> usize {
+        self.len()
+    }
+
+    fn entity(&self, index: usize) -> &Id {
+        &self[index].entity
+    }
+
+    fn seek(&self, from: usize, entity: &Id) -> usize {
+        (&self[..]).seek(from, entity)
+    }
+
+    fn fetch(&mut self, index: usize) -> Self::Item<'_> {
+        self.modify(index)
+    }
+}
+
+/// A tuple of [`JoinColumn`]s, addressed by their position in the tuple.
+pub trait JoinColumns {
+    const WIDTH: usize;
+
+    type Items<'a>
+    where
+        Self: 'a;
+
+    fn presence(column: usize) -> Presence;
+
+    fn rows(&self, column: usize) -> usize;
+
+    fn entity(&self, column: usize, index: usize) -> &Id;
+
+    fn seek(&self, column: usize, from: usize, entity: &Id) -> usize;
+
+    fn matches(&self, indices: &[usize]) -> bool;
+
+    fn fetch(&mut self, indices: &[usize]) -> Self::Items<'_>;
+}
+
+macro_rules! impl_join_columns {
+    ($width:expr; $( $column:ident $index:tt ),*) => {
+        impl<$( $column: JoinColumn ),*> JoinColumns for ($( $column, )*) {
+            const WIDTH: usize = $width;
+
+            type Items<'a> = ($( $column::Item<'a>, )*) where Self: 'a;
+
+            fn presence(column: usize) -> Presence {
+                match column {
+                    $( $index => $column::PRESENCE, )*
+                    _ => unreachable!("column out of range"),
+                }
+            }
+
+            fn rows(&self, column: usize) -> usize {
+                match column {
+                    $( $index => self.$index.rows(), )*
+                    _ => unreachable!("column out of range"),
+                }
+            }
+
+            fn entity(&self, column: usize, index: usize) -> &Id {
+                match column {
+                    $( $index => self.$index.entity(index), )*
+                    _ => unreachable!("column out of range"),
+                }
+            }
+
+            fn seek(&self, column: usize, from: usize, entity: &Id) -> usize {
+                match column {
+                    $( $index => self.$index.seek(from, entity), )*
+                    _ => unreachable!("column out of range"),
+                }
+            }
+
+            fn matches(&self, indices: &[usize]) -> bool {
+                $( self.$index.matches(indices[$index]) )&&*
+            }
+
+            fn fetch(&mut self, indices: &[usize]) -> Self::Items<'_> {
+                ($( self.$index.fetch(indices[$index]), )*)
+            }
+        }
+    };
+}
+
+impl_join_columns!(1; A 0);
+impl_join_columns!(2; A 0, B 1);
+impl_join_columns!(3; A 0, B 1, C 2);
+impl_join_columns!(4; A 0, B 1, C 2, D 3);
+impl_join_columns!(5; A 0, B 1, C 2, D 3, E 4);
+impl_join_columns!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+impl_join_columns!(7; A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_join_columns!(8; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_join_columns!(9; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_join_columns!(10; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+impl_join_columns!(11; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+impl_join_columns!(12; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+impl_join_columns!(13; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12);
+impl_join_columns!(14; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13);
+impl_join_columns!(15; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13, O 14);
+impl_join_columns!(16; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13, O 14, P 15);
+
+/// Joins the columns without a cached query, yielding the components of every entity
+/// present in all required columns, absent from all excluded ones, and with or without a
+/// row in each optional one.
+///
+/// Every column has to be sorted by entity, with each entity appearing at most once.
+/// Without a required column there is nothing to walk and the join is empty.
+pub fn join<C: JoinColumns>(columns: C) -> Join<C> {
+    let required: SmallVec<[usize; QUERY_INLINE]> = (0..C::WIDTH)
+        .filter(|&column| C::presence(column) == Presence::Required)
+        .collect();
+    let exhausted = required.is_empty() || required.iter().any(|&column| columns.rows(column) == 0);
+    let entity = if exhausted { [0; 16] } else { *columns.entity(required[0], 0) };
+    Join {
+        columns,
+        required,
+        cursors: smallvec![0; C::WIDTH],
+        indices: smallvec![MISSING; C::WIDTH],
+        entity,
+        column: 0,
+        agreeing: 1,
+        matched: false,
+        exhausted,
+    }
+}
+
+/// A leapfrog join: the required columns take turns galloping to the largest entity seen
+/// so far, and once all of them agree on it the optional and excluded columns are looked
+/// up for the entity.
+///
+/// Components are borrowed from the join itself, so it is advanced with `while let`
+/// instead of being an [`Iterator`].
+pub struct Join<C> {
+    columns: C,
+    /// Positions of the required columns in the tuple.
+    required: SmallVec<[usize; QUERY_INLINE]>,
+    cursors: SmallVec<[usize; QUERY_INLINE]>,
+    /// Rows of the current match, `MISSING` for optional and excluded columns without one.
+    indices: SmallVec<[usize; QUERY_INLINE]>,
+    entity: Id,
+    /// The required column visited last, as an index into `required`.
+    column: usize,
+    /// Number of consecutively visited required columns positioned at `entity`.
+    agreeing: usize,
+    /// Whether the columns are still positioned at the previously yielded match.
+    matched: bool,
+    exhausted: bool,
+}
+
+impl<C: JoinColumns> Join<C> {
+    pub fn next_match(&mut self) -> Option<C::Items<'_>> {
+        let width = self.required.len();
+        if self.exhausted {
+            return None;
+        }
+
+        loop {
+            if self.matched {
+                // Step past the previous match, its successor becomes the new target.
+                self.matched = false;
+                let column = self.required[self.column];
+                self.cursors[column] += 1;
+                if self.cursors[column] == self.columns.rows(column) {
+                    self.exhausted = true;
+                    return None;
+                }
+                self.entity = *self.columns.entity(column, self.cursors[column]);
+                self.agreeing = 1;
+            }
+
+            while self.agreeing < width {
+                self.column = (self.column + 1) % width;
+                let column = self.required[self.column];
+                let cursor = self.columns.seek(column, self.cursors[column], &self.entity);
+                if cursor == self.columns.rows(column) {
+                    self.exhausted = true;
+                    return None;
+                }
+                self.cursors[column] = cursor;
+
+                let found = self.columns.entity(column, cursor);
+                if *found == self.entity {
+                    self.agreeing += 1;
+                } else {
+                    self.entity = *found;
+                    self.agreeing = 1;
+                }
+            }
+
+            // Filtered out matches are stepped past like yielded ones.
+            self.matched = true;
+            if self.resolve() && self.columns.matches(&self.indices) {
+                return Some(self.columns.fetch(&self.indices));
+            }
+        }
+    }
+
+    /// Positions every column at the agreed on entity, returning false if it has a row in
+    /// an excluded column.
+    fn resolve(&mut self) -> bool {
+        for column in 0..C::WIDTH {
+            let presence = C::presence(column);
+            if presence == Presence::Required {
+                self.indices[column] = self.cursors[column];
+                continue;
+            }
+
+            // Matches only grow, so the cursor never has to move back.
+            let cursor = self.columns.seek(column, self.cursors[column], &self.entity);
+            self.cursors[column] = cursor;
+            let present = cursor < self.columns.rows(column) && *self.columns.entity(column, cursor) == self.entity;
+            match (presence, present) {
+                (Presence::Excluded, true) => return false,
+                (_, true) => self.indices[column] = cursor,
+                (_, false) => self.indices[column] = MISSING,
+            }
+        }
+        true
+    }
+}
+
+/// Skips the prefix of `slice` for which `cmp` holds, which has to be a prefix.
+pub(crate) fn gallop<T>(mut slice: &[T], mut cmp: impl FnMut(&T) -> bool) -> &[T] {
+    // if empty slice, or already >= element, return
+    if !slice.is_empty() && cmp(&slice[0]) {
+        let mut step = 1;
+        while step < slice.len() && cmp(&slice[step]) {
+            slice = &slice[step..];
+            step <<= 1;
+        }
+
+        step >>= 1;
+        while step > 0 {
+            if step < slice.len() && cmp(&slice[step]) {
+                slice = &slice[step..];
+            }
+            step >>= 1;
+        }
+
+        slice = &slice[1..]; // advance one, as we always stayed < value
+    }
+
+    slice
+}
@@ -1,29 +1,148 @@
-use std::any::Any;
-use std::collections::BTreeSet;
-use std::ops::{Deref, DerefMut};
+use std::any::{type_name, Any, TypeId};
+use std::collections::{hash_map, BTreeSet, HashMap, HashSet};
+use std::io;
+use std::ops::Deref;
 use std::sync::{atomic, Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use std::{cmp::Ordering, collections::BTreeMap};
 
 use std::borrow::Borrow;
 
-use arrayvec::ArrayVec;
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
 use rand::seq::index;
-use tribles::fucid;
 
+pub mod add_remove;
+pub mod archetype;
+mod bundle;
+pub mod change_detection;
+mod commands;
+pub mod deferred;
+pub mod despawn;
+mod event;
+pub mod events;
+pub mod filtered_iter;
+pub mod frag_iter;
+pub mod heavy_compute;
+mod hooks;
+mod ids;
+mod join;
+mod persist;
+mod query;
+pub mod random_access;
+mod registry;
+mod resource;
+pub mod schedule;
+pub mod serialize_binary;
+pub mod serialize_text;
 pub mod simple_insert;
 pub mod simple_iter;
+mod snapshot;
+mod system;
+pub mod transform_propagation;
+pub mod wide_insert;
+
+pub use bundle::Bundle;
+pub use commands::{Commands, Spawned, Target};
+pub use event::{EventReader, Events};
+pub use hooks::Hooks;
+pub use ids::{Fucid, IdAllocator, Ranges, Sequential};
+pub use join::{join, Join, JoinColumn, JoinColumns};
+pub use persist::{Components, Loader, Save};
+pub use query::{Added, Changed, ChangeFilter, Excluded, Fetch, Optional, ParQuery, Query, QueryData, RowsMut, Without};
+pub use registry::{resource_id, stable_id, ComponentError};
+pub use resource::{Resource, ResourceData, ResourceFetch, ResourceReadGuard, ResourceWriteGuard};
+pub use snapshot::{Blobs, Plain, PlainComponents, Snapshot};
+pub use system::{Access, Schedule, System};
+
+pub const NAME: &str = "local";
+/// Name of the `local` benchmarks that iterate by merge join instead of a cached query.
+pub const JOIN_NAME: &str = "local (join)";
+/// Name of the `local` benchmarks that index columns by hand, bypassing the typed queries.
+pub const UNCHECKED_NAME: &str = "local (unchecked)";
+/// Name of the `local` benchmarks whose entities have more components than fit inline.
+pub const SPILLED_NAME: &str = "local (spilled)";
+/// Name of the `local` benchmarks that run as systems of a [`Schedule`].
+pub const SYSTEM_NAME: &str = "local (system)";
+/// Name of the `local` benchmarks that defer their structural changes to a [`Commands`] buffer.
+pub const BATCHED_NAME: &str = "local (batched)";
+/// Name of the `local` benchmarks that only visit the rows changed since the last run.
+pub const CHANGED_NAME: &str = "local (changed)";
+/// Name of the `local` benchmarks that run hooks on their columns.
+pub const HOOKS_NAME: &str = "local (hooks)";
+/// Name of the `local` benchmarks that spawn their entities with [`World::spawn_batch`].
+pub const SPAWN_BATCH_NAME: &str = "local (spawn_batch)";
+/// Name of the `local` benchmarks that spawn their entities from one vector per component.
+pub const SOA_NAME: &str = "local (soa)";
+/// Name of the `local` benchmarks that despawn a hierarchy from its root.
+pub const RECURSIVE_NAME: &str = "local (recursive)";
+/// Name of the `local` benchmarks whose entities get sequential ids, see [`Sequential`].
+pub const SEQUENTIAL_NAME: &str = "local (sequential ids)";
+/// Name of the `local` benchmarks whose entities get ids from ranges reserved in bulk, see
+/// [`Ranges`].
+pub const RANGES_NAME: &str = "local (ranged ids)";
+/// Name of the `local` benchmarks that iterate by merge join over entities with sequential
+/// ids.
+pub const SEQUENTIAL_JOIN_NAME: &str = "local (join, sequential ids)";
+/// Name of the `local` benchmarks going through a memory-mapped snapshot instead of serde.
+pub const SNAPSHOT_NAME: &str = "local (snapshot)";
+/// Name of the `local` benchmarks going through a snapshot file instead of serde.
+pub const SNAPSHOT_FILE_NAME: &str = "local (snapshot file)";
 
 pub type Id = [u8; 16];
 
+/// Counts the write locks taken on a column, see [`Column::tick`].
+///
+/// The count wraps around, so ticks are compared by how many locks ago they were taken,
+/// see [`tick_is_newer`]. A change filter that doesn't run while a column takes more than
+/// [`MAX_TICK_AGE`] write locks may miss changes or report old rows as changed.
+pub type Tick = u32;
+
+/// Write locks between two passes clamping the ticks of a column's rows, see
+/// [`Column::write`].
+const CLAMP_TICKS_EVERY: Tick = 1 << 29;
+
+/// Age in write locks that older ticks are clamped to, leaving room below a full wrap of
+/// the clock for the age rows reach between two clamping passes.
+pub const MAX_TICK_AGE: Tick = Tick::MAX - 2 * CLAMP_TICKS_EVERY;
+
+/// Whether `tick` was taken after `seen`, both by a column whose clock now reads `now`.
+pub fn tick_is_newer(tick: Tick, seen: Tick, now: Tick) -> bool {
+    let age = now.wrapping_sub(tick).min(MAX_TICK_AGE);
+    let seen_age = now.wrapping_sub(seen).min(MAX_TICK_AGE);
+    age < seen_age
+}
+
+/// Clamps the ticks of rows older than [`MAX_TICK_AGE`] to that age, so that they don't
+/// look new again once the clock wraps around.
+fn clamp_ticks<T>(rows: &mut [Row<T>], now: Tick) {
+    let oldest = now.wrapping_sub(MAX_TICK_AGE);
+    for row in rows {
+        for tick in [&mut row.added, &mut row.changed] {
+            if now.wrapping_sub(*tick) > MAX_TICK_AGE {
+                *tick = oldest;
+            }
+        }
+    }
+}
+
+/// Laid out as declared, so that the rows written to a [`Snapshot`] are read back the same.
+#[repr(C)]
 pub struct Row<T> {
     pub entity: Id,
+    /// Tick of the write lock under which the row was added.
+    pub added: Tick,
+    /// Tick of the latest write lock under which the row was mutably accessed, see
+    /// [`ColumnWriteGuard::modify`].
+    pub changed: Tick,
     pub inner: T
 }
 
 impl<T> Row<T> {
-    fn new(entity: Id, inner: T) -> Self {
+    fn new(entity: Id, tick: Tick, inner: T) -> Self {
         Self {
             entity,
+            added: tick,
+            changed: tick,
             inner
         }
     }
@@ -55,30 +174,41 @@ impl<T> Borrow<Id> for Row<T> {
     }
 }
 
-const COMPONENT_LIMIT: usize = 16;
+/// Components an entity stores inline, more than that spill to the heap.
+const COMPONENTS_INLINE: usize = 16;
 
 #[derive(Clone)]
 pub struct Entity {
     pub id: Id,
-    pub component_id: ArrayVec<Id, COMPONENT_LIMIT>,
-    pub component_index: ArrayVec<usize, COMPONENT_LIMIT>
+    pub component_id: SmallVec<[Id; COMPONENTS_INLINE]>,
+    pub component_index: SmallVec<[usize; COMPONENTS_INLINE]>
 }
 
 impl Entity {
     fn new(id: Id) -> Self {
         Self {
             id,
-            component_id: ArrayVec::new(),
-            component_index: ArrayVec::new()
+            component_id: SmallVec::new(),
+            component_index: SmallVec::new()
         }
     }
 
     fn add_component<T: Send + Sync + 'static>(&mut self, column: &mut ColumnWriteGuard<T>, component: T) {
-        let index = column.len();
-        column.push(Row::new(self.id, component));
+        let index = column.push_row(self.id, component);
         self.component_id.push(column.id);
         self.component_index.push(index);
     }
+
+    fn index_of(&self, component: &Id) -> Option<usize> {
+        let indexindex = self.component_id.iter().position(|id| id == component)?;
+        Some(self.component_index[indexindex])
+    }
+
+    /// Points the entity's component at a new row of the column.
+    fn set_index(&mut self, component: &Id, index: usize) {
+        let indexindex = self.component_id.iter().position(|id| id == component).expect("entity has the component");
+        self.component_index[indexindex] = index;
+    }
 }
 
 impl Ord for Entity {
@@ -107,38 +237,189 @@ impl Borrow<Id> for Entity {
     }
 }
 
-trait ColumnData {
+/// Relates an entity to its parent, see [`World::set_parent`].
+///
+/// The world keeps a reverse index of the children of every parent, so the component is
+/// only added and removed through [`World::set_parent`] and [`World::remove_parent`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChildOf(Id);
+
+impl ChildOf {
+    pub fn parent(&self) -> &Id {
+        &self.0
+    }
+}
+
+trait ColumnData: Send + Sync {
     fn as_any(&self) -> &(dyn Any + Sync + Send);
+
+    /// Swap-removes the row at `index`, returning the entity whose row took its place
+    /// together with the index that row was moved from. `hooks` are the column's.
+    fn swap_remove_row(&self, index: usize, hooks: &(dyn Any + Sync + Send)) -> Option<(Id, usize)>;
+
+    /// Sorts the rows by entity, reporting the new index of every row.
+    fn sort_rows(&self, relocated: &mut dyn FnMut(&Id, usize));
+
+    /// Drops the rows of the entities not passing `keep`, preserving the order of the rest
+    /// and reporting the new index of every row that moved. `hooks` are the column's.
+    fn retain_rows(
+        &self,
+        keep: &dyn Fn(&Id) -> bool,
+        relocated: &mut dyn FnMut(&Id, usize),
+        hooks: &(dyn Any + Sync + Send),
+    );
 }
 
 impl<T: Sync + Send + 'static> ColumnData for RwLock<Vec<Row<T>>> {
     fn as_any(&self) -> &(dyn Any + Sync + Send) {
         self
     }
+
+    fn swap_remove_row(&self, index: usize, hooks: &(dyn Any + Sync + Send)) -> Option<(Id, usize)> {
+        let mut rows = self.write().expect("column lock poisoned");
+        let removed = rows.swap_remove(index);
+        column_hooks::<T>(hooks).removed(&removed.entity, &removed.inner);
+        let moved = rows.get(index)?;
+        Some((moved.entity, rows.len()))
+    }
+
+    fn sort_rows(&self, relocated: &mut dyn FnMut(&Id, usize)) {
+        let mut rows = self.write().expect("column lock poisoned");
+        rows.sort_unstable();
+        for (index, row) in rows.iter().enumerate() {
+            relocated(&row.entity, index);
+        }
+    }
+
+    fn retain_rows(
+        &self,
+        keep: &dyn Fn(&Id) -> bool,
+        relocated: &mut dyn FnMut(&Id, usize),
+        hooks: &(dyn Any + Sync + Send),
+    ) {
+        let mut rows = self.write().expect("column lock poisoned");
+        let mut kept = 0;
+        for index in 0..rows.len() {
+            if !keep(&rows[index].entity) {
+                continue;
+            }
+            if kept != index {
+                rows.swap(kept, index);
+                relocated(&rows[kept].entity, kept);
+            }
+            kept += 1;
+        }
+        let hooks = column_hooks::<T>(hooks);
+        for row in &rows[kept..] {
+            hooks.removed(&row.entity, &row.inner);
+        }
+        rows.truncate(kept);
+    }
+}
+
+fn column_hooks<T: 'static>(hooks: &(dyn Any + Sync + Send)) -> &Hooks<T> {
+    hooks.downcast_ref().expect("columns have hooks of their type")
 }
 
 #[derive(Clone)]
 pub struct Column {
     id: Id,
-    data: Arc<dyn ColumnData>
+    /// Type name of the stored components, for error messages.
+    component: &'static str,
+    data: Arc<dyn ColumnData>,
+    /// Tick of the latest write lock.
+    clock: Arc<atomic::AtomicU32>,
+    /// The [`Hooks`] of the stored type.
+    hooks: Arc<dyn Any + Sync + Send>,
+}
+
+/// The rows behind a [`ColumnReadGuard`], locked or mapped from a [`Snapshot`].
+enum ReadRows<'a, T> {
+    Locked(RwLockReadGuard<'a, Vec<Row<T>>>),
+    Mapped(&'a [Row<T>]),
 }
 
 pub struct ColumnReadGuard<'a, T> {
     pub id: Id,
-    guard: RwLockReadGuard<'a, Vec<Row<T>>>
+    rows: ReadRows<'a, T>
 }
 
 impl<'a, T> Deref for ColumnReadGuard<'a, T> {
-    type Target = Vec<Row<T>>;
+    type Target = [Row<T>];
 
     fn deref(&self) -> &Self::Target {
-        self.guard.deref()
+        match &self.rows {
+            ReadRows::Locked(guard) => guard,
+            ReadRows::Mapped(rows) => rows,
+        }
+    }
+}
+
+/// Shared access to the component of a single entity, see [`World::get`].
+pub struct ComponentRef<'a, T> {
+    guard: ColumnReadGuard<'a, T>,
+    index: usize
+}
+
+impl<'a, T> Deref for ComponentRef<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard[self.index].inner
     }
 }
 
+/// Exclusive access to a column. Components are only accessed mutably through
+/// [`ColumnWriteGuard::modify`] or a query, which stamp their rows with the guard's tick.
 pub struct ColumnWriteGuard<'a, T> {
     pub id: Id,
-    guard: RwLockWriteGuard<'a, Vec<Row<T>>>
+    pub tick: Tick,
+    guard: RwLockWriteGuard<'a, Vec<Row<T>>>,
+    hooks: &'a Hooks<T>
+}
+
+impl<'a, T> ColumnWriteGuard<'a, T> {
+    /// Mutable access to the component at `index`, marking it changed.
+    pub fn modify(&mut self, index: usize) -> &mut T {
+        let tick = self.tick;
+        let row = &mut self.guard[index];
+        row.changed = tick;
+        &mut row.inner
+    }
+
+    /// Like [`ColumnWriteGuard::modify`] without the bounds check.
+    ///
+    /// # Safety
+    /// `index` has to be less than the number of rows.
+    pub unsafe fn modify_unchecked(&mut self, index: usize) -> &mut T {
+        let tick = self.tick;
+        let row = self.guard.get_unchecked_mut(index);
+        row.changed = tick;
+        &mut row.inner
+    }
+
+    /// Appends a row for the entity and runs the column's add hook, returning its index.
+    fn push_row(&mut self, entity: Id, component: T) -> usize {
+        let index = self.len();
+        let tick = self.tick;
+        self.guard.push(Row::new(entity, tick, component));
+        self.hooks.added(&entity, &self[index].inner);
+        index
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.guard.reserve(additional);
+    }
+
+    /// Swap-removes the row at `index` without running the remove hook.
+    fn swap_remove(&mut self, index: usize) -> Row<T> {
+        self.guard.swap_remove(index)
+    }
+
+    /// The rows themselves, for queries that stamp the rows they hand out.
+    fn rows_mut(&mut self) -> &mut [Row<T>] {
+        &mut self.guard
+    }
 }
 
 impl<'a, T> Deref for ColumnWriteGuard<'a, T> {
@@ -149,45 +430,90 @@ impl<'a, T> Deref for ColumnWriteGuard<'a, T> {
     }
 }
 
-impl<'a, T> DerefMut for ColumnWriteGuard<'a, T> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        self.guard.deref_mut()
-    }
-}
-
 impl Column {
     pub fn new<T: Send + Sync + 'static>(id: Id) -> Self {
+        Self::with_hooks::<T>(id, Hooks::new())
+    }
+
+    pub fn with_hooks<T: Send + Sync + 'static>(id: Id, hooks: Hooks<T>) -> Self {
         Self {
             id,
-            data: Arc::new(RwLock::new(Vec::<Row<T>>::new()))
+            component: type_name::<T>(),
+            data: Arc::new(RwLock::new(Vec::<Row<T>>::new())),
+            clock: Arc::new(atomic::AtomicU32::new(0)),
+            hooks: Arc::new(hooks),
+        }
+    }
+
+    /// The number of write locks taken on the column so far, wrapping around. Rows whose
+    /// `added` or `changed` tick is newer, see [`tick_is_newer`], were stamped after this
+    /// was read.
+    pub fn tick(&self) -> Tick {
+        self.clock.load(atomic::Ordering::Relaxed)
+    }
+
+    fn stores<T: Send + Sync + 'static>(&self) -> bool {
+        self.lock::<T>().is_ok() || self.mapped::<T>().is_some()
+    }
+
+    /// The rows of the column if they are mapped from a snapshot, see
+    /// [`World::map_snapshot`].
+    fn mapped<T: Send + Sync + 'static>(&self) -> Option<&snapshot::MappedRows<T>> {
+        ColumnData::as_any(&*self.data).downcast_ref::<snapshot::MappedRows<T>>()
+    }
+
+    fn lock<T: Send + Sync + 'static>(&self) -> Result<&RwLock<Vec<Row<T>>>, ComponentError> {
+        let data = &*self.data;
+        let data = ColumnData::as_any(data);
+        data.downcast_ref::<RwLock<Vec<Row<T>>>>().ok_or(ComponentError::TypeMismatch {
+            id: self.id,
+            component: self.component,
+            requested: type_name::<T>(),
+        })
+    }
+
+    fn poisoned(&self) -> ComponentError {
+        ComponentError::Poisoned {
+            id: self.id,
+            component: self.component,
         }
     }
 
-    pub fn read<'a, T>(&'a self) -> Option<ColumnReadGuard<'a, T>>
+    pub fn read<'a, T>(&'a self) -> Result<ColumnReadGuard<'a, T>, ComponentError>
     where
         T: Send + Sync + 'static,
     {
-        let data = &*self.data;
-        let data = ColumnData::as_any(data);
-        let lock = data.downcast_ref::<RwLock<Vec<Row<T>>>>()?;
-        let guard = lock.read().ok()?;
-        Some(ColumnReadGuard {
+        let rows = match self.mapped::<T>() {
+            Some(mapped) => ReadRows::Mapped(mapped.rows()),
+            None => ReadRows::Locked(self.lock::<T>()?.read().map_err(|_| self.poisoned())?),
+        };
+        Ok(ColumnReadGuard {
             id: self.id,
-            guard
+            rows
         })
     }
 
-    pub fn write<'a, T>(&'a self) -> Option<ColumnWriteGuard<'a, T>>
+    pub fn write<'a, T>(&'a self) -> Result<ColumnWriteGuard<'a, T>, ComponentError>
     where
         T: Send + Sync + 'static,
     {
-        let data = &*self.data;
-        let data = ColumnData::as_any(data);
-        let lock = data.downcast_ref::<RwLock<Vec<Row<T>>>>()?;
-        let guard = lock.write().ok()?;
-        Some(ColumnWriteGuard {
+        if self.mapped::<T>().is_some() {
+            return Err(ComponentError::ReadOnly {
+                id: self.id,
+                component: self.component,
+            });
+        }
+        let mut guard = self.lock::<T>()?.write().map_err(|_| self.poisoned())?;
+        // Bumped while holding the lock, so ticks are handed out in locking order.
+        let tick = self.clock.fetch_add(1, atomic::Ordering::Relaxed).wrapping_add(1);
+        if tick & (CLAMP_TICKS_EVERY - 1) == 0 {
+            clamp_ticks(&mut guard, tick);
+        }
+        Ok(ColumnWriteGuard {
             id: self.id,
-            guard
+            tick,
+            guard,
+            hooks: column_hooks(&*self.hooks)
         })
     }
 }
@@ -218,121 +544,937 @@ impl Borrow<Id> for Column {
     }
 }
 
-const QUERY_LIMIT: usize = 8;
+/// Width up to which query keys and matches are stored inline.
+const QUERY_INLINE: usize = 8;
+
+/// How an entity is matched against a column of a query.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Presence {
+    /// The entity has a row in the column.
+    Required,
+    /// The entity may have a row in the column, the match lists [`MISSING`] if it doesn't.
+    Optional,
+    /// The entity has no row in the column, the match always lists [`MISSING`].
+    Excluded,
+}
+
+/// A column of a query and how entities are matched against it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Term {
+    pub id: Id,
+    pub presence: Presence,
+}
+
+impl Term {
+    pub fn required(id: Id) -> Self {
+        Term { id, presence: Presence::Required }
+    }
+}
+
+/// Which change ticks the filter of a query term compares, see [`Added`] and [`Changed`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Tracking {
+    /// The term has no change filter.
+    Untracked,
+    Added,
+    Changed,
+}
+
+/// Row index listed by a match for a column the entity has no row in, see [`Presence`].
+pub const MISSING: usize = usize::MAX;
+
+/// The columns of a cached query, in the order its matches list row indices.
+pub type QueryKey = SmallVec<[Term; QUERY_INLINE]>;
+
+/// For each column of a query, the column tick up to which its change filter has seen the
+/// rows, see [`Changed`].
+pub type Seen = SmallVec<[atomic::AtomicU32; QUERY_INLINE]>;
+
+/// The terms of a query and the change filter of each, under which the world keeps its
+/// [`Seen`] state. Queries over the same columns with different filters don't share it.
+pub type SeenKey = (QueryKey, SmallVec<[Tracking; QUERY_INLINE]>);
+
+fn unseen(width: usize) -> Seen {
+    (0..width).map(|_| atomic::AtomicU32::new(0)).collect()
+}
+
+/// The cached matches of a query, each listing a row index per term of its key.
+///
+/// A match is found through the row of its entity in the column of the first required
+/// term, the anchor, so dropping or patching one doesn't search the whole list.
+pub struct Matches {
+    /// The row indices listed by the matches, one after the other.
+    indices: Vec<usize>,
+    width: usize,
+    /// Position of the anchor term in the key.
+    anchor: usize,
+    /// For each row of the anchor column, the number of the match listing it, or
+    /// [`MISSING`] if its entity doesn't match.
+    positions: Vec<usize>,
+}
+
+impl Matches {
+    fn new(components: &[Term]) -> Self {
+        let anchor = components
+            .iter()
+            .position(|term| term.presence == Presence::Required)
+            .expect("a query needs at least one required component");
+        Matches { indices: Vec::new(), width: components.len(), anchor, positions: Vec::new() }
+    }
+
+    fn position(&self, anchor_row: usize) -> Option<usize> {
+        self.positions.get(anchor_row).copied().filter(|&position| position != MISSING)
+    }
+
+    fn set_position(&mut self, anchor_row: usize, position: usize) {
+        if self.positions.len() <= anchor_row {
+            self.positions.resize(anchor_row + 1, MISSING);
+        }
+        self.positions[anchor_row] = position;
+    }
+
+    fn push(&mut self, matched: &[usize]) {
+        self.set_position(matched[self.anchor], self.indices.len() / self.width);
+        self.indices.extend_from_slice(matched);
+    }
+
+    /// Swap-removes the match listing `matched`.
+    fn remove(&mut self, matched: &[usize]) {
+        let position = match self.position(matched[self.anchor]) {
+            Some(position) => position,
+            None => return,
+        };
+        let (start, last) = (position * self.width, self.indices.len() - self.width);
+        if start != last {
+            for offset in 0..self.width {
+                self.indices.swap(start + offset, last + offset);
+            }
+            self.positions[self.indices[start + self.anchor]] = position;
+        }
+        self.indices.truncate(last);
+        self.positions[matched[self.anchor]] = MISSING;
+    }
+
+    /// Patches the match of an entity whose row for the term at `term` moved from `from`
+    /// to `to`, with `anchor_row` its row in the anchor column after the move.
+    fn relocate(&mut self, term: usize, anchor_row: usize, from: usize, to: usize) {
+        let row = if term == self.anchor { from } else { anchor_row };
+        let position = match self.position(row) {
+            Some(position) => position,
+            None => return,
+        };
+        self.indices[position * self.width + term] = to;
+        if term == self.anchor {
+            self.positions[from] = MISSING;
+            self.set_position(to, position);
+        }
+    }
+}
 
 pub struct World {
     pub entities: Vec<Entity>,
-    pub queries: BTreeMap<ArrayVec<Id, QUERY_LIMIT>, Vec<usize>>,
-    pub components: BTreeSet<Column>
+    /// The index into `entities` of each entity id, see [`World::slot`].
+    pub slots: HashMap<Id, usize>,
+    pub queries: BTreeMap<QueryKey, Matches>,
+    pub components: BTreeSet<Column>,
+    /// The column id each component type was registered with, see [`World::register`].
+    pub types: HashMap<TypeId, Id>,
+    /// Columns whose rows are no longer sorted by entity, see [`World::sort_columns`].
+    pub unsorted: BTreeSet<Id>,
+    /// Change filter state of the queries run through the world, see [`World::query`].
+    pub seen: BTreeMap<SeenKey, Seen>,
+    pub resources: HashMap<TypeId, Resource>,
+    /// Updates each of the event queues added to the world, see [`World::update_events`].
+    pub event_queues: Vec<fn(&mut World)>,
+    /// The children of every entity that has any, in the order they were attached, see
+    /// [`ChildOf`].
+    pub children: HashMap<Id, Vec<Id>>,
+    /// Allocates the ids of new entities, see [`World::with_ids`].
+    pub ids: Box<dyn IdAllocator>
 }
 
 impl World {
     fn new() -> Self {
+        Self::with_ids(Fucid)
+    }
+
+    /// Creates an empty world whose entities get their ids from the allocator.
+    fn with_ids(ids: impl IdAllocator + 'static) -> Self {
         Self {
             entities: Vec::new(),
+            slots: HashMap::new(),
             queries: BTreeMap::new(),
-            components: BTreeSet::new()
+            components: BTreeSet::new(),
+            types: HashMap::new(),
+            unsorted: BTreeSet::new(),
+            seen: BTreeMap::new(),
+            resources: HashMap::new(),
+            event_queues: Vec::new(),
+            children: HashMap::new(),
+            ids: Box::new(ids)
         }
     }
 }
 
+/// Mutable access to an entity, adding components through it keeps the query caches
+/// of the world up to date.
+pub struct EntityMut<'a> {
+    world: &'a mut World,
+    slot: usize
+}
+
+impl<'a> EntityMut<'a> {
+    /// Adds the component to the entity, replacing and returning the previous value if the
+    /// entity already had one.
+    fn add_component<T: Send + Sync + 'static>(&mut self, column: &mut ColumnWriteGuard<T>, component: T) -> Option<T> {
+        let entity = &mut self.world.entities[self.slot];
+        if let Some(index) = entity.index_of(&column.id) {
+            let previous = std::mem::replace(column.modify(index), component);
+            column.hooks.replaced(&entity.id, &previous, &column[index].inner);
+            return Some(previous);
+        }
+
+        if column.last().is_some_and(|last| last.entity > entity.id) {
+            self.world.unsorted.insert(column.id);
+        }
+        entity.add_component(column, component);
+        rematch_queries(&mut self.world.queries, entity, &column.id, true);
+        None
+    }
+}
+
+impl<'a> Deref for EntityMut<'a> {
+    type Target = Entity;
+
+    fn deref(&self) -> &Self::Target {
+        &self.world.entities[self.slot]
+    }
+}
+
 impl World {
-    fn new_entity(&mut self) -> &mut Entity {
-        let id = fucid();
-        let entity = Entity::new(id);
-        self.entities.push(entity);
-        self.entities.last_mut().expect("just pushed")
+    /// Creates an entity without components, which therefore doesn't match any query yet.
+    fn new_entity(&mut self) -> EntityMut<'_> {
+        let slot = self.entities.len();
+        let id = self.claim_id(slot);
+        self.entities.push(Entity::new(id));
+        EntityMut {
+            slot,
+            world: self
+        }
+    }
+
+    /// Allocates an id no entity has yet and indexes it as the one in `slot`.
+    fn claim_id(&mut self, slot: usize) -> Id {
+        loop {
+            let id = self.ids.next();
+            if let hash_map::Entry::Vacant(vacant) = self.slots.entry(id) {
+                vacant.insert(slot);
+                return id;
+            }
+        }
+    }
+
+    /// Like [`World::claim_id`] for `count` slots from `start` on, allocating the ids in
+    /// bulk.
+    fn claim_ids(&mut self, start: usize, count: usize) -> Vec<Id> {
+        let mut ids = Vec::with_capacity(count);
+        self.ids.allocate(count, &mut ids);
+        self.slots.reserve(count);
+        for (offset, id) in ids.iter_mut().enumerate() {
+            let vacant = match self.slots.entry(*id) {
+                hash_map::Entry::Vacant(vacant) => {
+                    vacant.insert(start + offset);
+                    true
+                }
+                hash_map::Entry::Occupied(_) => false,
+            };
+            if !vacant {
+                *id = self.claim_id(start + offset);
+            }
+        }
+        ids
+    }
+
+    /// Like [`World::new_entity`], but under a given id, which may not be taken.
+    fn spawn(&mut self, id: Id) -> Result<EntityMut<'_>, ComponentError> {
+        let slot = self.entities.len();
+        match self.slots.entry(id) {
+            hash_map::Entry::Occupied(_) => return Err(ComponentError::EntityExists { id }),
+            hash_map::Entry::Vacant(vacant) => vacant.insert(slot),
+        };
+        self.entities.push(Entity::new(id));
+        Ok(EntityMut {
+            slot,
+            world: self
+        })
+    }
+
+    /// Spawns an entity for each bundle of components, e.g. `(Position, Velocity)`,
+    /// returning the spawned entities.
+    ///
+    /// Unlike spawning them one by one with [`World::new_entity`], each column is locked and
+    /// reserved once, the ids for the iterator's size hint are allocated in bulk, and the
+    /// cached queries are extended in a single pass at the end. Types that aren't
+    /// registered yet are registered, see [`World::register`]. None of the bundle's columns
+    /// may be borrowed while spawning.
+    fn spawn_batch<B: Bundle>(&mut self, bundles: impl IntoIterator<Item = B>) -> Result<&[Entity], ComponentError> {
+        let columns = self.bundle_columns::<B>()?;
+        let mut guards = B::lock(&columns)?;
+        let ends = B::ends(&guards);
+
+        let bundles = bundles.into_iter();
+        let start = self.entities.len();
+        let mut ids = self.claim_ids(start, bundles.size_hint().0);
+        B::reserve(&mut guards, ids.len());
+        let mut spawned = 0;
+        for bundle in bundles {
+            let id = match ids.get(spawned) {
+                Some(id) => *id,
+                None => {
+                    let id = self.claim_id(start + spawned);
+                    ids.push(id);
+                    id
+                }
+            };
+            bundle.push(&mut guards, id);
+            spawned += 1;
+        }
+        // An iterator yielding fewer bundles than its size hint leaves claimed ids unused.
+        for id in ids.drain(spawned..) {
+            self.slots.remove(&id);
+        }
+        drop(guards);
+
+        Ok(self.index_spawned(&columns, &ends, ids))
+    }
+
+    /// Like [`World::spawn_batch`], but with the components as one vector per type, e.g.
+    /// `(Vec<Position>, Vec<Velocity>)`, which must all have the same length.
+    ///
+    /// The ids are allocated in bulk up front and the columns filled one after the other.
+    fn spawn_soa<B: Bundle>(&mut self, soa: B::Soa) -> Result<&[Entity], ComponentError> {
+        let len = B::soa_len(&soa).ok_or(ComponentError::UnevenBatch)?;
+        let columns = self.bundle_columns::<B>()?;
+        let mut guards = B::lock(&columns)?;
+        let ends = B::ends(&guards);
+
+        let ids = self.claim_ids(self.entities.len(), len);
+        B::reserve(&mut guards, len);
+        B::extend(soa, &mut guards, &ids);
+        drop(guards);
+
+        Ok(self.index_spawned(&columns, &ends, ids))
+    }
+
+    /// The columns of the bundle's types, rejecting bundles with a type more than once.
+    fn bundle_columns<B: Bundle>(&mut self) -> Result<SmallVec<[Column; COMPONENTS_INLINE]>, ComponentError> {
+        let columns = B::columns(self)?;
+        for (i, column) in columns.iter().enumerate() {
+            if columns[..i].contains(column) {
+                return Err(ComponentError::Aliased { component: column.component });
+            }
+        }
+        Ok(columns)
     }
 
-    fn new_component<T: Send + Sync + 'static>(&mut self, id: Id) -> Column {
-        let col = Column::new::<T>(id);
+    /// Creates the entities with the claimed ids whose rows were just appended to the
+    /// columns, which had the given row counts and last entities before, and adds their
+    /// matches to the cached queries.
+    fn index_spawned(&mut self, columns: &[Column], ends: &[(usize, Option<Id>)], ids: Vec<Id>) -> &[Entity] {
+        let ascending = ids.windows(2).all(|pair| pair[0] < pair[1]);
+        for (column, (_, last)) in columns.iter().zip(ends) {
+            let behind = ids.first().is_some_and(|first| last.is_some_and(|last| last > *first));
+            if behind || !ascending {
+                self.unsorted.insert(column.id);
+            }
+        }
+
+        let start = self.entities.len();
+        self.entities.reserve(ids.len());
+        for (offset, id) in ids.into_iter().enumerate() {
+            self.entities.push(Entity {
+                id,
+                component_id: columns.iter().map(|column| column.id).collect(),
+                component_index: ends.iter().map(|(len, _)| len + offset).collect()
+            });
+        }
+
+        let spawned = &self.entities[start..];
+        for (components, matches) in self.queries.iter_mut() {
+            for entity in spawned {
+                if let Some(matched) = match_entity(components, entity, None) {
+                    matches.push(&matched);
+                }
+            }
+        }
+        spawned
+    }
+
+    /// Creates the column storing components of type `T`, with an id derived from the type
+    /// name, see [`stable_id`].
+    fn register<T: Send + Sync + 'static>(&mut self) -> Result<Column, ComponentError> {
+        self.new_component::<T>(stable_id::<T>())
+    }
+
+    /// Creates the column storing components of type `T` under a user assigned id.
+    ///
+    /// Registering a type again under the same id returns its existing column, while
+    /// registering it under another id, or reusing the id of another type, is an error.
+    fn new_component<T: Send + Sync + 'static>(&mut self, id: Id) -> Result<Column, ComponentError> {
+        self.new_component_with_hooks::<T>(id, Hooks::new())
+    }
+
+    /// Like [`World::new_component`], running the hooks whenever a component of type `T` is
+    /// added to, replaced in or removed from the column.
+    ///
+    /// Hooks can only be given when the column is created, passing them for an existing
+    /// column is an error.
+    fn new_component_with_hooks<T: Send + Sync + 'static>(&mut self, id: Id, hooks: Hooks<T>) -> Result<Column, ComponentError> {
+        if let Some(&registered) = self.types.get(&TypeId::of::<T>()) {
+            if registered != id {
+                return Err(ComponentError::Reregistered {
+                    component: type_name::<T>(),
+                    id: registered,
+                    requested: id,
+                });
+            }
+        }
+        if let Some(col) = self.components.get(&id) {
+            if !col.stores::<T>() {
+                return Err(ComponentError::IdTaken {
+                    id,
+                    component: col.component,
+                    requested: type_name::<T>(),
+                });
+            }
+            if !hooks.is_empty() {
+                return Err(ComponentError::HooksTooLate { component: col.component });
+            }
+            return Ok(col.clone());
+        }
+
+        let col = Column::with_hooks::<T>(id, hooks);
         self.components.insert(col.clone());
-        col
+        self.types.insert(TypeId::of::<T>(), id);
+        Ok(col)
+    }
+
+    fn component_id<T: 'static>(&self) -> Result<Id, ComponentError> {
+        self.types.get(&TypeId::of::<T>()).copied().ok_or(ComponentError::Unregistered {
+            component: type_name::<T>(),
+        })
     }
 
-    fn new_query(&mut self, components: &ArrayVec<Id, QUERY_LIMIT>) {
-        if let None = self.queries.get(components) {
-            let vec = Vec::new();
-            self.queries.insert(components.clone(), vec);
-            let vec = self.queries.get_mut(components).expect("just inserted");
+    /// The column registered for `T`.
+    fn column<T: 'static>(&self) -> Result<&Column, ComponentError> {
+        let id = self.component_id::<T>()?;
+        Ok(self.components.get(&id).expect("registered types have a column"))
+    }
+
+    /// Inserts the resource of type `T`, replacing and returning the previous one.
+    fn insert_resource<T: Send + Sync + 'static>(&mut self, resource: T) -> Option<T> {
+        match self.resources.get_mut(&TypeId::of::<T>()) {
+            Some(previous) => Some(std::mem::replace(previous.get_mut::<T>(), resource)),
+            None => {
+                self.resources.insert(TypeId::of::<T>(), Resource::new(resource));
+                None
+            }
+        }
+    }
+
+    /// Inserts an empty [`Events`] queue for events of type `T` as a resource, unless there
+    /// already is one, to be updated by [`World::update_events`].
+    fn add_events<T: Send + Sync + 'static>(&mut self) {
+        if self.resources.contains_key(&TypeId::of::<Events<T>>()) {
+            return;
+        }
+        self.insert_resource(Events::<T>::new());
+        self.event_queues.push(|world| {
+            let queue = world.resources.get_mut(&TypeId::of::<Events<T>>()).expect("event queues aren't removed");
+            queue.get_mut::<Events<T>>().update();
+        });
+    }
+
+    /// Updates every event queue added with [`World::add_events`], dropping the events sent
+    /// before the previous update. Usually called once per frame, after the systems ran.
+    fn update_events(&mut self) {
+        for update in self.event_queues.clone() {
+            update(self);
+        }
+    }
+
+    fn resource<T: Send + Sync + 'static>(&self) -> Result<ResourceReadGuard<'_, T>, ComponentError> {
+        self.resources
+            .get(&TypeId::of::<T>())
+            .ok_or(ComponentError::NoResource { resource: type_name::<T>() })?
+            .read()
+    }
+
+    fn resource_mut<T: Send + Sync + 'static>(&self) -> Result<ResourceWriteGuard<'_, T>, ComponentError> {
+        self.resources
+            .get(&TypeId::of::<T>())
+            .ok_or(ComponentError::NoResource { resource: type_name::<T>() })?
+            .write()
+    }
+
+    /// Caches the matches of the query, which needs a required term, see [`QueryData::key`].
+    fn new_query(&mut self, components: &QueryKey) {
+        debug_assert!(components.iter().any(|term| term.presence == Presence::Required));
+        if !self.queries.contains_key(components) {
+            let mut matches = Matches::new(components);
             for entity in self.entities.iter() {
-                if components.iter().all(|component| entity.component_id.contains(component)) {
-                    for component_id in components {
-                        let indexindex = entity.component_id.iter().position(|id|id == component_id).expect("is a subset");
-                        let index = entity.component_index[indexindex];
-                        vec.push(index);
-                    }
+                if let Some(matched) = match_entity(components, entity, None) {
+                    matches.push(&matched);
                 }
             }
+            self.queries.insert(components.clone(), matches);
         }
     }
 
-    fn query(&mut self, components: &ArrayVec<Id, QUERY_LIMIT>) -> Option<impl Iterator<Item = &[usize]>> {
-        let indices = self.queries.get(components)?;
-        Some(indices.chunks_exact(components.len()))
+    fn slot(&self, id: &Id) -> Option<usize> {
+        self.slots.get(id).copied()
     }
 
-    //fn prepare_queries(&self, world; &mut World) {}
-}
+    /// The component of type `T` of the entity, or `None` if it doesn't have one.
+    ///
+    /// The column stays read locked while the returned reference is alive.
+    fn get<T: Send + Sync + 'static>(&self, id: &Id) -> Result<Option<ComponentRef<'_, T>>, ComponentError> {
+        let slot = self.slot(id).ok_or(ComponentError::NoEntity { id: *id })?;
+        let column = self.column::<T>()?;
+        let index = match self.entities[slot].index_of(&column.id) {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+        Ok(Some(ComponentRef {
+            guard: column.read()?,
+            index
+        }))
+    }
 
+    fn entity_mut(&mut self, id: &Id) -> Option<EntityMut<'_>> {
+        let slot = self.slot(id)?;
+        Some(EntityMut { world: self, slot })
+    }
 
-/*
-fn join_helper<K: Ord, V1, V2>(
-    mut slice1: &[(K, V1)],
-    mut slice2: &[(K, V2)],
-    mut result: impl FnMut(&K, &V1, &V2),
-) {
-    while !slice1.is_empty() && !slice2.is_empty() {
-        use std::cmp::Ordering;
+    /// Removes the entity and all of its components, returns `false` if there is no such entity.
+    ///
+    /// Rows are swap-removed from their columns, so the entity owning the last row of a
+    /// column gets its `component_index` patched, as do the cached queries referencing it.
+    /// Children of the entity are left without a parent, see [`World::despawn_recursive`].
+    /// None of the entity's columns may be borrowed while despawning.
+    fn despawn(&mut self, id: &Id) -> bool {
+        let slot = match self.slot(id) {
+            Some(slot) => slot,
+            None => return false,
+        };
+        let orphans = self.unlink(std::slice::from_ref(id), |other| other == id);
+        let entity = self.entities.swap_remove(slot);
+        self.slots.remove(&entity.id);
+        if let Some(moved) = self.entities.get(slot) {
+            self.slots.insert(moved.id, slot);
+        }
+
+        unmatch_queries(&mut self.queries, &entity);
 
-        // If the keys match produce tuples, else advance the smaller key until they might.
-        match slice1[0].0.cmp(&slice2[0].0) {
-            Ordering::Less => {
-                slice1 = gallop(slice1, |x| x.0 < slice2[0].0);
+        for (component, &index) in entity.component_id.iter().zip(entity.component_index.iter()) {
+            let column = self.components.get(component).expect("entity has a column for each component");
+            if let Some((moved, from)) = column.data.swap_remove_row(index, &*column.hooks) {
+                self.relocate_row(component, moved, from, index);
             }
-            Ordering::Equal => {
-                // Determine the number of matching keys in each slice.
-                let count1 = slice1.iter().take_while(|x| x.0 == slice1[0].0).count();
-                let count2 = slice2.iter().take_while(|x| x.0 == slice2[0].0).count();
-
-                // Produce results from the cross-product of matches.
-                for index1 in 0..count1 {
-                    for s2 in slice2[..count2].iter() {
-                        result(&slice1[0].0, &slice1[index1].1, &s2.1);
-                    }
-                }
+        }
+
+        self.orphan(&orphans);
+        true
+    }
+
+    /// Removes all of the entities at once, ignoring ids without an entity.
+    ///
+    /// Instead of swap-removing row by row like [`World::despawn`], every column is
+    /// compacted in a single pass that keeps the order of the remaining rows. The matches of
+    /// the despawned entities are dropped from the cached queries, and the rows that moved
+    /// are patched in them. None of the columns may be borrowed while despawning.
+    fn despawn_batch(&mut self, doomed: &HashSet<Id>) {
+        if doomed.is_empty() {
+            return;
+        }
+        let orphans = self.unlink(doomed, |other| doomed.contains(other));
+
+        for entity in self.entities.iter().filter(|entity| doomed.contains(&entity.id)) {
+            unmatch_queries(&mut self.queries, entity);
+        }
+        self.entities.retain(|entity| !doomed.contains(&entity.id));
+        self.slots = self.entities.iter().enumerate().map(|(slot, entity)| (entity.id, slot)).collect();
+        for column in &self.components {
+            let (entities, slots, queries) = (&mut self.entities, &self.slots, &mut self.queries);
+            column.data.retain_rows(
+                &|entity| !doomed.contains(entity),
+                &mut |entity, index| {
+                    let entity = &mut entities[slots[entity]];
+                    let from = entity.index_of(&column.id).expect("rows belong to entities");
+                    entity.set_index(&column.id, index);
+                    relocate_matches(queries, entity, &column.id, from, index);
+                },
+                &*column.hooks,
+            );
+        }
+        self.orphan(&orphans);
+    }
 
-                // Advance slices past this key.
-                slice1 = &slice1[count1..];
-                slice2 = &slice2[count2..];
+    /// Removes the entity together with its children, their children and so on, returns
+    /// `false` if there is no such entity.
+    ///
+    /// The subtree is removed at once through [`World::despawn_batch`], with the same
+    /// restrictions on borrowed columns.
+    fn despawn_recursive(&mut self, id: &Id) -> bool {
+        if self.slot(id).is_none() {
+            return false;
+        }
+
+        let mut doomed = HashSet::new();
+        let mut stack = vec![*id];
+        while let Some(next) = stack.pop() {
+            stack.extend_from_slice(self.children(&next));
+            doomed.insert(next);
+        }
+        self.despawn_batch(&doomed);
+        true
+    }
+
+    /// Makes `child` a child of `parent` by giving it a [`ChildOf`] component, moving it
+    /// away from its previous parent.
+    ///
+    /// An entity can't become a child of itself or of one of its descendants. The
+    /// `ChildOf` column may not be borrowed while reparenting.
+    fn set_parent(&mut self, child: &Id, parent: &Id) -> Result<(), ComponentError> {
+        for id in [child, parent] {
+            if self.slot(id).is_none() {
+                return Err(ComponentError::NoEntity { id: *id });
             }
-            Ordering::Greater => {
-                slice2 = gallop(slice2, |x| x.0 < slice1[0].0);
+        }
+        if self.parent(child) == Some(*parent) {
+            return Ok(());
+        }
+        let mut ancestor = Some(*parent);
+        while let Some(id) = ancestor {
+            if id == *child {
+                return Err(ComponentError::Cycle { child: *child, parent: *parent });
             }
+            ancestor = self.parent(&id);
         }
+
+        let column = self.register::<ChildOf>()?;
+        self.remove_parent(child)?;
+        let mut rows = column.write::<ChildOf>()?;
+        let mut entity = self.entity_mut(child).expect("checked to exist");
+        entity.add_component(&mut rows, ChildOf(*parent));
+        self.children.entry(*parent).or_default().push(*child);
+        Ok(())
     }
-}
 
-pub(crate) fn gallop<T>(mut slice: &[T], mut cmp: impl FnMut(&T) -> bool) -> &[T] {
-    // if empty slice, or already >= element, return
-    if !slice.is_empty() && cmp(&slice[0]) {
-        let mut step = 1;
-        while step < slice.len() && cmp(&slice[step]) {
-            slice = &slice[step..];
-            step <<= 1;
+    /// Removes the [`ChildOf`] component of the entity, returning its former parent.
+    ///
+    /// The `ChildOf` column may not be borrowed while reparenting.
+    fn remove_parent(&mut self, child: &Id) -> Result<Option<Id>, ComponentError> {
+        let column = self.column::<ChildOf>()?.clone();
+        let mut rows = column.write::<ChildOf>()?;
+        let parent = match self.remove_component(child, &mut rows) {
+            Some(ChildOf(parent)) => parent,
+            None => return Ok(None),
+        };
+        self.unlink_child(&parent, child);
+        Ok(Some(parent))
+    }
+
+    fn parent(&self, child: &Id) -> Option<Id> {
+        let child_of = self.get::<ChildOf>(child).ok()??;
+        Some(*child_of.parent())
+    }
+
+    /// The children of the entity, in the order they were attached.
+    fn children(&self, parent: &Id) -> &[Id] {
+        self.children.get(parent).map_or(&[], |children| &children[..])
+    }
+
+    fn unlink_child(&mut self, parent: &Id, child: &Id) {
+        if let hash_map::Entry::Occupied(mut children) = self.children.entry(*parent) {
+            children.get_mut().retain(|other| other != child);
+            if children.get().is_empty() {
+                children.remove();
+            }
+        }
+    }
+
+    /// Drops the entities about to be despawned from the children of their parents and
+    /// from the reverse index, returning their children that are `!doomed`.
+    fn unlink<'d>(&mut self, despawned: impl IntoIterator<Item = &'d Id>, doomed: impl Fn(&Id) -> bool) -> Vec<Id> {
+        // Without any children there is no `ChildOf` row to look at.
+        if self.children.is_empty() {
+            return Vec::new();
         }
+        let column = match self.column::<ChildOf>() {
+            Ok(column) => column.clone(),
+            Err(_) => return Vec::new(),
+        };
+        let rows = column.read::<ChildOf>().expect("the ChildOf column isn't borrowed while despawning");
 
-        step >>= 1;
-        while step > 0 {
-            if step < slice.len() && cmp(&slice[step]) {
-                slice = &slice[step..];
+        let mut orphans = Vec::new();
+        for id in despawned {
+            let parent = self
+                .slot(id)
+                .and_then(|slot| self.entities[slot].index_of(&column.id))
+                .map(|index| rows[index].inner.0);
+            if let Some(parent) = parent.filter(|parent| !doomed(parent)) {
+                self.unlink_child(&parent, id);
             }
-            step >>= 1;
+            if let Some(children) = self.children.remove(id) {
+                orphans.extend(children.into_iter().filter(|child| !doomed(child)));
+            }
+        }
+        orphans
+    }
+
+    /// Removes the [`ChildOf`] component of the children whose parent was despawned.
+    fn orphan(&mut self, orphans: &[Id]) {
+        if orphans.is_empty() {
+            return;
+        }
+        let column = self.column::<ChildOf>().expect("orphans had a parent").clone();
+        let mut rows = column.write::<ChildOf>().expect("the ChildOf column isn't borrowed while despawning");
+        for child in orphans {
+            self.remove_component(child, &mut rows);
         }
+    }
 
-        slice = &slice[1..]; // advance one, as we always stayed < value
+    /// Rebuilds the reverse index of [`ChildOf`] from its column, see [`World::children`].
+    fn index_children(&mut self) {
+        self.children.clear();
+        let column = match self.column::<ChildOf>() {
+            Ok(column) => column.clone(),
+            Err(_) => return,
+        };
+        let rows = column.read::<ChildOf>().expect("the ChildOf column isn't borrowed while loading");
+        for row in rows.iter() {
+            self.children.entry(row.inner.0).or_default().push(row.entity);
+        }
     }
 
-    slice
+    /// Removes a single component from the entity, returning it if the entity had one.
+    ///
+    /// Like [`World::despawn`] the row is swap-removed from the column, with the same
+    /// fixups for the entity owning the moved row.
+    fn remove_component<T: Send + Sync + 'static>(&mut self, id: &Id, column: &mut ColumnWriteGuard<T>) -> Option<T> {
+        let slot = self.slot(id)?;
+        let indexindex = self.entities[slot].component_id.iter().position(|id| *id == column.id)?;
+
+        rematch_queries(&mut self.queries, &self.entities[slot], &column.id, false);
+
+        let entity = &mut self.entities[slot];
+        entity.component_id.swap_remove(indexindex);
+        let index = entity.component_index.swap_remove(indexindex);
+
+        let removed = column.swap_remove(index);
+        column.hooks.removed(id, &removed.inner);
+        if let Some(moved) = column.get(index) {
+            let (moved, from) = (moved.entity, column.len());
+            self.relocate_row(&column.id, moved, from, index);
+        }
+
+        Some(removed.inner)
+    }
+
+    /// Patches the entity `moved` and the cached queries after its row in the column
+    /// `component` was moved from `from` to `to`.
+    fn relocate_row(&mut self, component: &Id, moved: Id, from: usize, to: usize) {
+        self.unsorted.insert(*component);
+
+        let slot = self.slot(&moved).expect("rows belong to entities");
+        self.entities[slot].set_index(component, to);
+
+        relocate_matches(&mut self.queries, &self.entities[slot], component, from, to);
+    }
+
+    fn query_indices(&self, components: &QueryKey) -> Option<impl Iterator<Item = &[usize]>> {
+        let matches = self.queries.get(components)?;
+        Some(matches.indices.chunks_exact(components.len()))
+    }
+
+    /// Iterates the entities matching `Q`, e.g. `(&mut Position, &Velocity)` or
+    /// `(&mut Position, Option<&Drag>, Without<Frozen>)`, through the cached query for
+    /// those terms, which is created on first use.
+    ///
+    /// The columns stay locked while the query is alive, so none of them may be borrowed
+    /// elsewhere. Change filters like [`Changed`] match the rows changed since the previous
+    /// query with the same terms and filters.
+    fn query<Q: QueryData>(&mut self) -> Result<Query<'_, Q::Columns<'_>>, ComponentError> {
+        let key = Q::key(self)?;
+        self.new_query(&key);
+        let seen = self.new_seen::<Q>(&key);
+        self.cached_query::<Q>(&key, &self.seen[&seen])
+    }
+
+    /// Like [`World::query`], but the matches are split between the threads of the rayon pool.
+    fn par_query<Q: QueryData>(&mut self) -> Result<ParQuery<'_, Q>, ComponentError> {
+        let key = Q::key(self)?;
+        self.new_query(&key);
+        let seen = self.new_seen::<Q>(&key);
+        let indices = &self.queries.get(&key).expect("query was just created").indices;
+        let columns = Q::lock(self, &key, &self.seen[&seen])?;
+        Ok(ParQuery::new(columns, indices, key.len()))
+    }
+
+    /// Like [`World::query`] with shared access to the world, for a query over the
+    /// terms `key` of `Q` that was created beforehand, with its own change filter state.
+    fn cached_query<Q: QueryData>(&self, key: &QueryKey, seen: &Seen) -> Result<Query<'_, Q::Columns<'_>>, ComponentError> {
+        let indices = self.queries.get(key).expect("query was created beforehand").indices.chunks_exact(key.len());
+        let columns = Q::lock(self, key, seen)?;
+        Ok(Query::new(columns, indices))
+    }
+
+    /// Like [`World::query`], but as a merge join over the columns, see [`join`].
+    fn join<Q: QueryData>(&mut self) -> Result<Join<Q::Columns<'_>>, ComponentError> {
+        let key = Q::key(self)?;
+        self.sort_columns();
+        let seen = self.new_seen::<Q>(&key);
+        let columns = Q::lock(self, &key, &self.seen[&seen])?;
+        Ok(join(columns))
+    }
+
+    /// Borrows the world for serialization with serde, saving its entities, the columns
+    /// storing one of the types `C` and the keys of its cached queries.
+    ///
+    /// Change ticks and change filter state are not saved. None of the saved columns may be
+    /// borrowed mutably while serializing.
+    fn save<C: Components>(&self) -> Save<'_, C> {
+        Save::new(self)
+    }
+
+    /// Deserializes a world saved with [`World::save`] with the same types `C`, registering
+    /// the columns under their saved ids and rebuilding the saved cached queries, as well as
+    /// the children of each parent if `C` includes [`ChildOf`].
+    fn load<'de, C: Components, D: serde::Deserializer<'de>>(deserializer: D) -> Result<World, D::Error> {
+        persist::load::<C, D>(deserializer)
+    }
+
+    /// Writes the entity ids and the rows of the columns storing one of the types `C` as
+    /// they are in memory, to be mapped back read-only with [`Snapshot::open`].
+    ///
+    /// Cached queries, resources and change filter state are not written. None of the
+    /// written columns may be borrowed mutably while writing.
+    fn snapshot<C: PlainComponents>(&self, out: impl io::Write) -> io::Result<()> {
+        snapshot::write::<C, _>(self, out)
+    }
+
+    /// Creates a world of the snapshot's entities, whose columns storing one of the types
+    /// `C` are the rows mapped from the snapshot, without copying them.
+    ///
+    /// The mapped columns can be read and queried like any other, but writing to them is an
+    /// error, see [`ComponentError::ReadOnly`], and despawning entities with rows in them,
+    /// or sorting them, panics. Cached queries have to be rebuilt.
+    fn map_snapshot<C: PlainComponents>(snapshot: Snapshot) -> Result<World, ComponentError> {
+        snapshot::map::<C>(snapshot)
+    }
+
+    /// Creates the change filter state of `Q` over the terms `components` if needed,
+    /// returning its key.
+    fn new_seen<Q: QueryData>(&mut self, components: &QueryKey) -> SeenKey {
+        let key = (components.clone(), Q::tracking());
+        if !self.seen.contains_key(&key) {
+            self.seen.insert(key.clone(), unseen(components.len()));
+        }
+        key
+    }
+
+    /// Restores the order by entity of every column that lost it, as required by [`join`].
+    ///
+    /// The entities and cached queries referencing the sorted columns are patched to the
+    /// new row indices. None of those columns may be borrowed while sorting.
+    fn sort_columns(&mut self) {
+        if self.unsorted.is_empty() {
+            return;
+        }
+
+        let unsorted = std::mem::take(&mut self.unsorted);
+        for component in &unsorted {
+            let column = self.components.get(component).expect("only existing columns get out of order");
+            let (entities, slots) = (&mut self.entities, &self.slots);
+            column.data.sort_rows(&mut |entity, index| entities[slots[entity]].set_index(component, index));
+        }
+
+        let stale: Vec<QueryKey> = self.queries
+            .keys()
+            .filter(|components| components.iter().any(|term| unsorted.contains(&term.id)))
+            .cloned()
+            .collect();
+        for components in stale {
+            self.queries.remove(&components);
+            self.new_query(&components);
+        }
+    }
+
+    //fn prepare_queries(&self, world; &mut World) {}
+}
+
+/// The row indices listed by the match of `entity` in the query, or `None` if it doesn't
+/// match. The column `absent` is treated as if the entity had no row in it.
+fn match_entity(components: &[Term], entity: &Entity, absent: Option<&Id>) -> Option<SmallVec<[usize; QUERY_INLINE]>> {
+    components
+        .iter()
+        .map(|term| {
+            let index = entity.index_of(&term.id).filter(|_| absent != Some(&term.id));
+            match (term.presence, index) {
+                (Presence::Required, index) => index,
+                (Presence::Optional, index) => Some(index.unwrap_or(MISSING)),
+                (Presence::Excluded, None) => Some(MISSING),
+                (Presence::Excluded, Some(_)) => None,
+            }
+        })
+        .collect()
+}
+
+/// Updates the cached match of `entity` in every query involving `component`, which the
+/// entity has just gained a row in if `added`, or is about to lose its row in otherwise.
+///
+/// Depending on the query this adds or drops the match, or lists the row for an optional
+/// component in it.
+fn rematch_queries(
+    queries: &mut BTreeMap<QueryKey, Matches>,
+    entity: &Entity,
+    component: &Id,
+    added: bool,
+) {
+    for (components, matches) in queries.iter_mut() {
+        if !components.iter().any(|term| term.id == *component) {
+            continue;
+        }
+        let without = match_entity(components, entity, Some(component));
+        let with = match_entity(components, entity, None);
+        let (before, after) = if added { (without, with) } else { (with, without) };
+        if let Some(before) = before {
+            matches.remove(&before);
+        }
+        if let Some(after) = after {
+            matches.push(&after);
+        }
+    }
+}
+
+/// Patches the cached match of `entity` in every query involving `component`, after its
+/// row in that column moved from `from` to `to`.
+fn relocate_matches(queries: &mut BTreeMap<QueryKey, Matches>, entity: &Entity, component: &Id, from: usize, to: usize) {
+    for (components, matches) in queries.iter_mut() {
+        if let Some(term) = components.iter().position(|term| term.id == *component) {
+            if let Some(anchor_row) = entity.index_of(&components[matches.anchor].id) {
+                matches.relocate(term, anchor_row, from, to);
+            }
+        }
+    }
+}
+
+/// Drops the cached matches of `entity` from every query.
+fn unmatch_queries(queries: &mut BTreeMap<QueryKey, Matches>, entity: &Entity) {
+    for (components, matches) in queries.iter_mut() {
+        if let Some(matched) = match_entity(components, entity, None) {
+            matches.remove(&matched);
+        }
+    }
 }
-*/
\ No newline at end of file
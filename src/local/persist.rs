@@ -0,0 +1,269 @@
+use std::any::type_name;
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::{self, DeserializeOwned, DeserializeSeed, Deserializer, SeqAccess, Visitor};
+use serde::ser::{self, Serialize, SerializeSeq, SerializeTuple, Serializer};
+
+use super::{Column, Entity, Id, Presence, QueryKey, Row, Term, World};
+
+/// A tuple of the component types a [`World`] is saved and loaded with, e.g.
+/// `(Position, Velocity)`, see [`World::save`].
+///
+/// Columns are matched to their type by its name, so a column can be loaded under a user
+/// assigned id like the one it was saved with.
+pub trait Components {
+    /// Whether the column stores one of the types.
+    fn stores(column: &Column) -> bool;
+
+    /// Serializes the column as an `(id, type name, rows)` element of `seq`, if it stores one
+    /// of the types.
+    fn save_column<S: SerializeSeq>(column: &Column, seq: &mut S) -> Result<(), S::Error>;
+
+    /// Creates the column of the type named `component` and loads its rows from the next
+    /// element of `seq`.
+    fn load_column<'de, A: SeqAccess<'de>>(
+        loader: &mut Loader,
+        id: Id,
+        component: &str,
+        seq: &mut A,
+    ) -> Result<(), A::Error>;
+}
+
+macro_rules! impl_components {
+    ($( $component:ident ),*) => {
+        impl<$( $component ),*> Components for ($( $component, )*)
+        where
+            $( $component: Serialize + DeserializeOwned + Send + Sync + 'static ),*
+        {
+            fn stores(column: &Column) -> bool {
+                $( column.stores::<$component>() )||*
+            }
+
+            fn save_column<S: SerializeSeq>(column: &Column, seq: &mut S) -> Result<(), S::Error> {
+                $(
+                    if column.stores::<$component>() {
+                        let rows = column.read::<$component>().map_err(ser::Error::custom)?;
+                        return seq.serialize_element(&(column.id, column.component, SavedRows(&rows[..])));
+                    }
+                )*
+                Ok(())
+            }
+
+            fn load_column<'de, Elements: SeqAccess<'de>>(
+                loader: &mut Loader,
+                id: Id,
+                component: &str,
+                seq: &mut Elements,
+            ) -> Result<(), Elements::Error> {
+                $(
+                    if component == type_name::<$component>() {
+                        let rows: Vec<(Id, $component)> = seq
+                            .next_element()?
+                            .ok_or_else(|| de::Error::invalid_length(2, &"a column of id, type name and rows"))?;
+                        return loader.rows(id, rows);
+                    }
+                )*
+                Err(de::Error::custom(format_args!("component `{}` is not one of the loaded types", component)))
+            }
+        }
+    };
+}
+
+impl_components!(A);
+impl_components!(A, B);
+impl_components!(A, B, C);
+impl_components!(A, B, C, D);
+impl_components!(A, B, C, D, E);
+impl_components!(A, B, C, D, E, F);
+impl_components!(A, B, C, D, E, F, G);
+impl_components!(A, B, C, D, E, F, G, H);
+impl_components!(A, B, C, D, E, F, G, H, I);
+impl_components!(A, B, C, D, E, F, G, H, I, J);
+impl_components!(A, B, C, D, E, F, G, H, I, J, K);
+impl_components!(A, B, C, D, E, F, G, H, I, J, K, L);
+impl_components!(A, B, C, D, E, F, G, H, I, J, K, L, M);
+impl_components!(A, B, C, D, E, F, G, H, I, J, K, L, M, N);
+impl_components!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
+impl_components!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
+
+/// A world borrowed for serialization, as a tuple of its entity ids, its columns storing
+/// one of the types `C` and the keys of its cached queries.
+pub struct Save<'w, C> {
+    world: &'w World,
+    components: PhantomData<C>,
+}
+
+impl<'w, C> Save<'w, C> {
+    pub(super) fn new(world: &'w World) -> Self {
+        Self {
+            world,
+            components: PhantomData,
+        }
+    }
+}
+
+impl<C: Components> Serialize for Save<'_, C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let queries: Vec<&[Term]> = self.world.queries.keys().map(|key| &key[..]).collect();
+
+        let mut tuple = serializer.serialize_tuple(3)?;
+        tuple.serialize_element(&SavedEntities(&self.world.entities))?;
+        tuple.serialize_element(&SavedColumns::<C>(self.world, PhantomData))?;
+        tuple.serialize_element(&queries)?;
+        tuple.end()
+    }
+}
+
+struct SavedEntities<'w>(&'w [Entity]);
+
+impl Serialize for SavedEntities<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(|entity| &entity.id))
+    }
+}
+
+struct SavedColumns<'w, C>(&'w World, PhantomData<C>);
+
+impl<C: Components> Serialize for SavedColumns<'_, C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let saved = self.0.components.iter().filter(|column| C::stores(column)).count();
+        let mut seq = serializer.serialize_seq(Some(saved))?;
+        for column in &self.0.components {
+            C::save_column(column, &mut seq)?;
+        }
+        seq.end()
+    }
+}
+
+struct SavedRows<'c, T>(&'c [Row<T>]);
+
+impl<T: Serialize> Serialize for SavedRows<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(|row| (&row.entity, &row.inner)))
+    }
+}
+
+/// A world being deserialized, see [`Components::load_column`].
+pub struct Loader {
+    world: World,
+}
+
+impl Loader {
+    /// Pushes the rows into the newly created column `id`, rejecting rows of unknown
+    /// entities and entities that already have a row in it.
+    fn rows<T: Send + Sync + 'static, E: de::Error>(&mut self, id: Id, rows: Vec<(Id, T)>) -> Result<(), E> {
+        let column = self.world.new_component::<T>(id).map_err(E::custom)?;
+        let mut column = column.write::<T>().map_err(E::custom)?;
+        if !column.is_empty() {
+            return Err(E::custom(format_args!("column of `{}` occurs twice", type_name::<T>())));
+        }
+
+        column.reserve(rows.len());
+        for (entity, inner) in rows {
+            let slot = self.world.slot(&entity).ok_or_else(|| E::custom("row of an unknown entity"))?;
+            let owner = &mut self.world.entities[slot];
+            if owner.index_of(&id).is_some() {
+                return Err(E::custom("entity has two rows in one column"));
+            }
+            owner.component_id.push(id);
+            owner.component_index.push(column.push_row(entity, inner));
+        }
+
+        if !column.windows(2).all(|pair| pair[0].entity < pair[1].entity) {
+            self.world.unsorted.insert(id);
+        }
+        Ok(())
+    }
+}
+
+/// Deserializes a world saved with [`World::save`], see [`World::load`].
+pub(super) fn load<'de, C: Components, D: Deserializer<'de>>(deserializer: D) -> Result<World, D::Error> {
+    deserializer.deserialize_tuple(3, WorldVisitor::<C>(PhantomData))
+}
+
+struct WorldVisitor<C>(PhantomData<C>);
+
+impl<'de, C: Components> Visitor<'de> for WorldVisitor<C> {
+    type Value = World;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a world of entities, columns and queries")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<World, A::Error> {
+        let entities: Vec<Id> = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+
+        let mut loader = Loader { world: World::new() };
+        loader.world.entities.reserve(entities.len());
+        loader.world.slots.reserve(entities.len());
+        for id in entities {
+            loader.world.spawn(id).map_err(de::Error::custom)?;
+        }
+
+        seq.next_element_seed(ColumnsSeed::<C>(&mut loader, PhantomData))?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        let queries: Vec<Vec<Term>> = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(2, &self))?;
+        let mut world = loader.world;
+        world.index_children();
+        for query in queries {
+            let query: QueryKey = query.into_iter().collect();
+            // Queries over columns that weren't saved would never match, and ones without a
+            // required term can't be cached.
+            let required = query.iter().any(|term| term.presence == Presence::Required);
+            if required && query.iter().all(|term| world.components.contains(&term.id)) {
+                world.new_query(&query);
+            }
+        }
+
+        Ok(world)
+    }
+}
+
+struct ColumnsSeed<'l, C>(&'l mut Loader, PhantomData<C>);
+
+impl<'de, C: Components> DeserializeSeed<'de> for ColumnsSeed<'_, C> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, C: Components> Visitor<'de> for ColumnsSeed<'_, C> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence of columns")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while seq.next_element_seed(ColumnSeed::<C>(&mut *self.0, PhantomData))?.is_some() {}
+        Ok(())
+    }
+}
+
+struct ColumnSeed<'l, C>(&'l mut Loader, PhantomData<C>);
+
+impl<'de, C: Components> DeserializeSeed<'de> for ColumnSeed<'_, C> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_tuple(3, self)
+    }
+}
+
+impl<'de, C: Components> Visitor<'de> for ColumnSeed<'_, C> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a column of id, type name and rows")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let id: Id = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let component: String = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        C::load_column(self.0, id, &component, &mut seq)
+    }
+}
@@ -0,0 +1,495 @@
+use std::marker::PhantomData;
+use std::slice::ChunksExact;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use rayon::prelude::*;
+use smallvec::{smallvec, SmallVec};
+
+use super::{tick_is_newer, Access, Column, ColumnReadGuard, ColumnWriteGuard, ComponentError, Id, JoinColumn, JoinColumns, Presence, QueryKey, Row, Term, Tick, Tracking, World, MISSING, QUERY_INLINE};
+
+/// Shared or exclusive access to a component type, the building block of [`QueryData`].
+pub trait Fetch {
+    /// The guard holding the column lock while the query is alive.
+    type Column<'w>: JoinColumn;
+
+    /// Whether the column is locked for writing, see [`Access`].
+    const WRITE: bool;
+
+    /// How entities are matched against the column.
+    const PRESENCE: Presence = Presence::Required;
+
+    /// Which change ticks the column's filter compares.
+    const TRACKING: Tracking = Tracking::Untracked;
+
+    fn id(world: &World) -> Result<Id, ComponentError>;
+
+    /// Locks the column, `seen` is the tick up to which a change filter has seen its rows.
+    fn lock<'w>(column: &'w Column, seen: &AtomicU32) -> Result<Self::Column<'w>, ComponentError>;
+
+    /// The rows of the locked column, shared between the threads of a [`ParQuery`].
+    type Rows<'c>: Copy + Send + Sync;
+
+    type Item<'c>: Send;
+
+    fn rows<'c>(column: &'c mut Self::Column<'_>) -> Self::Rows<'c>;
+
+    /// Whether the row passes the filter, see [`JoinColumn::matches`].
+    fn matches(_rows: Self::Rows<'_>, _index: usize) -> bool {
+        true
+    }
+
+    /// # Safety
+    ///
+    /// For a column locked for writing, the row at `index` must not be fetched again while
+    /// the returned item is alive.
+    unsafe fn fetch<'c>(rows: Self::Rows<'c>, index: usize) -> Self::Item<'c>;
+}
+
+impl<T: Send + Sync + 'static> Fetch for &T {
+    type Column<'w> = ColumnReadGuard<'w, T>;
+
+    const WRITE: bool = false;
+
+    fn id(world: &World) -> Result<Id, ComponentError> {
+        world.component_id::<T>()
+    }
+
+    fn lock<'w>(column: &'w Column, _seen: &AtomicU32) -> Result<Self::Column<'w>, ComponentError> {
+        column.read()
+    }
+
+    type Rows<'c> = &'c [Row<T>];
+
+    type Item<'c> = &'c T;
+
+    fn rows<'c>(column: &'c mut Self::Column<'_>) -> Self::Rows<'c> {
+        &column[..]
+    }
+
+    unsafe fn fetch<'c>(rows: Self::Rows<'c>, index: usize) -> Self::Item<'c> {
+        &rows[index].inner
+    }
+}
+
+impl<T: Send + Sync + 'static> Fetch for &mut T {
+    type Column<'w> = ColumnWriteGuard<'w, T>;
+
+    const WRITE: bool = true;
+
+    fn id(world: &World) -> Result<Id, ComponentError> {
+        world.component_id::<T>()
+    }
+
+    fn lock<'w>(column: &'w Column, _seen: &AtomicU32) -> Result<Self::Column<'w>, ComponentError> {
+        column.write()
+    }
+
+    type Rows<'c> = RowsMut<'c, T>;
+
+    type Item<'c> = &'c mut T;
+
+    fn rows<'c>(column: &'c mut Self::Column<'_>) -> Self::Rows<'c> {
+        RowsMut {
+            rows: column.rows_mut().as_mut_ptr(),
+            len: column.len(),
+            tick: column.tick,
+            marker: PhantomData,
+        }
+    }
+
+    unsafe fn fetch<'c>(rows: Self::Rows<'c>, index: usize) -> Self::Item<'c> {
+        assert!(index < rows.len, "row {} out of bounds", index);
+        let row = &mut *rows.rows.add(index);
+        row.changed = rows.tick;
+        &mut row.inner
+    }
+}
+
+/// The rows of a column locked for writing, handed out one at a time to the threads of
+/// a [`ParQuery`] and marked as changed when they are.
+pub struct RowsMut<'c, T> {
+    rows: *mut Row<T>,
+    len: usize,
+    tick: Tick,
+    marker: PhantomData<&'c mut [Row<T>]>,
+}
+
+impl<T> Clone for RowsMut<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for RowsMut<'_, T> {}
+
+// Sharing the rows hands out `&mut T`s to other threads, which is sending `T`.
+unsafe impl<T: Send> Send for RowsMut<'_, T> {}
+unsafe impl<T: Send> Sync for RowsMut<'_, T> {}
+
+/// Shared access to the components of type `T` added since the query last ran.
+pub struct Added<T>(PhantomData<T>);
+
+/// Shared access to the components of type `T` added or mutably accessed since the query
+/// last ran.
+pub struct Changed<T>(PhantomData<T>);
+
+/// The rows of [`Added`] or [`Changed`], passing those with a tick newer than `seen`.
+#[derive(Clone, Copy)]
+pub struct ChangeFilter<R> {
+    rows: R,
+    seen: Tick,
+    /// The column's tick when it was locked, which the ages of the ticks are counted from.
+    now: Tick,
+    added: bool,
+}
+
+impl<T> ChangeFilter<&[Row<T>]> {
+    fn passes(&self, index: usize) -> bool {
+        let row = &self.rows[index];
+        let tick = if self.added { row.added } else { row.changed };
+        tick_is_newer(tick, self.seen, self.now)
+    }
+}
+
+impl<T> JoinColumn for ChangeFilter<ColumnReadGuard<'_, T>> {
+    type Item<'a> = &'a T where Self: 'a;
+
+    fn rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn entity(&self, index: usize) -> &Id {
+        &self.rows[index].entity
+    }
+
+    fn seek(&self, from: usize, entity: &Id) -> usize {
+        self.rows.seek(from, entity)
+    }
+
+    fn matches(&self, index: usize) -> bool {
+        ChangeFilter { rows: &self.rows[..], seen: self.seen, now: self.now, added: self.added }.passes(index)
+    }
+
+    fn fetch(&mut self, index: usize) -> Self::Item<'_> {
+        &self.rows[index].inner
+    }
+}
+
+macro_rules! impl_change_filter {
+    ($filter:ident, $added:expr) => {
+        impl<T: Send + Sync + 'static> Fetch for $filter<T> {
+            type Column<'w> = ChangeFilter<ColumnReadGuard<'w, T>>;
+
+            const WRITE: bool = false;
+
+            const TRACKING: Tracking = Tracking::$filter;
+
+            fn id(world: &World) -> Result<Id, ComponentError> {
+                world.component_id::<T>()
+            }
+
+            fn lock<'w>(column: &'w Column, seen: &AtomicU32) -> Result<Self::Column<'w>, ComponentError> {
+                let rows = column.read()?;
+                // No writer can get in while the rows are locked, so every row stamped
+                // later is newer than the column's current tick.
+                let now = column.tick();
+                let seen = seen.swap(now, Ordering::Relaxed);
+                Ok(ChangeFilter { rows, seen, now, added: $added })
+            }
+
+            type Rows<'c> = ChangeFilter<&'c [Row<T>]>;
+
+            type Item<'c> = &'c T;
+
+            fn rows<'c>(column: &'c mut Self::Column<'_>) -> Self::Rows<'c> {
+                ChangeFilter { rows: &column.rows[..], seen: column.seen, now: column.now, added: column.added }
+            }
+
+            fn matches(rows: Self::Rows<'_>, index: usize) -> bool {
+                rows.passes(index)
+            }
+
+            unsafe fn fetch<'c>(rows: Self::Rows<'c>, index: usize) -> Self::Item<'c> {
+                &rows.rows[index].inner
+            }
+        }
+    };
+}
+
+impl_change_filter!(Added, true);
+impl_change_filter!(Changed, false);
+
+/// The column of an `Option<F>`, fetching `None` for entities without a row in it.
+pub struct Optional<C>(C);
+
+impl<C: JoinColumn> JoinColumn for Optional<C> {
+    type Item<'a> = Option<C::Item<'a>> where Self: 'a;
+
+    const PRESENCE: Presence = Presence::Optional;
+
+    fn rows(&self) -> usize {
+        self.0.rows()
+    }
+
+    fn entity(&self, index: usize) -> &Id {
+        self.0.entity(index)
+    }
+
+    fn seek(&self, from: usize, entity: &Id) -> usize {
+        self.0.seek(from, entity)
+    }
+
+    fn matches(&self, index: usize) -> bool {
+        index == MISSING || self.0.matches(index)
+    }
+
+    fn fetch(&mut self, index: usize) -> Self::Item<'_> {
+        if index == MISSING {
+            None
+        } else {
+            Some(self.0.fetch(index))
+        }
+    }
+}
+
+/// Access to a component the entity may lack, e.g. `Option<&T>`. Entities with one still
+/// have to pass its filter, like that of `Option<Changed<T>>`.
+impl<F: Fetch> Fetch for Option<F> {
+    type Column<'w> = Optional<F::Column<'w>>;
+
+    const WRITE: bool = F::WRITE;
+
+    const PRESENCE: Presence = Presence::Optional;
+
+    const TRACKING: Tracking = F::TRACKING;
+
+    fn id(world: &World) -> Result<Id, ComponentError> {
+        F::id(world)
+    }
+
+    fn lock<'w>(column: &'w Column, seen: &AtomicU32) -> Result<Self::Column<'w>, ComponentError> {
+        Ok(Optional(F::lock(column, seen)?))
+    }
+
+    type Rows<'c> = F::Rows<'c>;
+
+    type Item<'c> = Option<F::Item<'c>>;
+
+    fn rows<'c>(column: &'c mut Self::Column<'_>) -> Self::Rows<'c> {
+        F::rows(&mut column.0)
+    }
+
+    fn matches(rows: Self::Rows<'_>, index: usize) -> bool {
+        index == MISSING || F::matches(rows, index)
+    }
+
+    unsafe fn fetch<'c>(rows: Self::Rows<'c>, index: usize) -> Self::Item<'c> {
+        if index == MISSING {
+            None
+        } else {
+            Some(F::fetch(rows, index))
+        }
+    }
+}
+
+/// Matches the entities without a component of type `T`, fetching `()`.
+pub struct Without<T>(PhantomData<T>);
+
+/// The column of a [`Without`], locked for reading so that a [`join`](super::join) can
+/// look for the entity in it.
+pub struct Excluded<C>(C);
+
+impl<C: JoinColumn> JoinColumn for Excluded<C> {
+    type Item<'a> = () where Self: 'a;
+
+    const PRESENCE: Presence = Presence::Excluded;
+
+    fn rows(&self) -> usize {
+        self.0.rows()
+    }
+
+    fn entity(&self, index: usize) -> &Id {
+        self.0.entity(index)
+    }
+
+    fn seek(&self, from: usize, entity: &Id) -> usize {
+        self.0.seek(from, entity)
+    }
+
+    fn fetch(&mut self, _index: usize) -> Self::Item<'_> {}
+}
+
+impl<T: Send + Sync + 'static> Fetch for Without<T> {
+    type Column<'w> = Excluded<ColumnReadGuard<'w, T>>;
+
+    const WRITE: bool = false;
+
+    const PRESENCE: Presence = Presence::Excluded;
+
+    fn id(world: &World) -> Result<Id, ComponentError> {
+        world.component_id::<T>()
+    }
+
+    fn lock<'w>(column: &'w Column, _seen: &AtomicU32) -> Result<Self::Column<'w>, ComponentError> {
+        Ok(Excluded(column.read()?))
+    }
+
+    type Rows<'c> = ();
+
+    type Item<'c> = ();
+
+    fn rows<'c>(_column: &'c mut Self::Column<'_>) -> Self::Rows<'c> {}
+
+    unsafe fn fetch<'c>(_rows: Self::Rows<'c>, _index: usize) -> Self::Item<'c> {}
+}
+
+/// A tuple of [`Fetch`]es, e.g. `(&mut Position, &Velocity)`, see [`World::query`].
+pub trait QueryData {
+    type Columns<'w>: JoinColumns;
+
+    /// Terms in tuple order.
+    ///
+    /// At least one term has to be required, entities matching none of the terms would
+    /// otherwise have to be matched whenever they are spawned or changed.
+    fn key(world: &World) -> Result<QueryKey, ComponentError>;
+
+    /// The change filter of each term in tuple order, see [`SeenKey`](super::SeenKey).
+    fn tracking() -> SmallVec<[Tracking; QUERY_INLINE]>;
+
+    /// The columns read and written, given the terms returned by [`QueryData::key`].
+    fn access(key: &[Term]) -> Access;
+
+    /// Locks the columns, with the change filter state `seen` of the query.
+    fn lock<'w>(world: &'w World, key: &[Term], seen: &[AtomicU32]) -> Result<Self::Columns<'w>, ComponentError>;
+
+    type Rows<'c>: Copy + Send + Sync;
+
+    type Items<'c>: Send;
+
+    fn rows<'c>(columns: &'c mut Self::Columns<'_>) -> Self::Rows<'c>;
+
+    fn matches(rows: Self::Rows<'_>, indices: &[usize]) -> bool;
+
+    /// # Safety
+    ///
+    /// See [`Fetch::fetch`], for each of the `indices` in tuple order.
+    unsafe fn fetch<'c>(rows: Self::Rows<'c>, indices: &[usize]) -> Self::Items<'c>;
+}
+
+macro_rules! impl_query_data {
+    ($( $fetch:ident $index:tt ),*) => {
+        impl<$( $fetch: Fetch ),*> QueryData for ($( $fetch, )*) {
+            type Columns<'w> = ($( $fetch::Column<'w>, )*);
+
+            fn key(world: &World) -> Result<QueryKey, ComponentError> {
+                let mut key = QueryKey::new();
+                $( key.push(Term { id: $fetch::id(world)?, presence: $fetch::PRESENCE }); )*
+                for (index, term) in key.iter().enumerate() {
+                    if key[..index].iter().any(|other| other.id == term.id) {
+                        let column = world.components.get(&term.id).expect("registered types have a column");
+                        return Err(ComponentError::Aliased { component: column.component });
+                    }
+                }
+                if !key.iter().any(|term| term.presence == Presence::Required) {
+                    return Err(ComponentError::NothingRequired);
+                }
+                Ok(key)
+            }
+
+            fn tracking() -> SmallVec<[Tracking; QUERY_INLINE]> {
+                smallvec![$( $fetch::TRACKING ),*]
+            }
+
+            fn access(key: &[Term]) -> Access {
+                let mut access = Access::default();
+                $( access.declare(key[$index].id, $fetch::WRITE); )*
+                access
+            }
+
+            fn lock<'w>(world: &'w World, key: &[Term], seen: &[AtomicU32]) -> Result<Self::Columns<'w>, ComponentError> {
+                Ok(($( $fetch::lock(world.components.get(&key[$index].id).expect("registered types have a column"), &seen[$index])?, )*))
+            }
+
+            type Rows<'c> = ($( $fetch::Rows<'c>, )*);
+
+            type Items<'c> = ($( $fetch::Item<'c>, )*);
+
+            fn rows<'c>(columns: &'c mut Self::Columns<'_>) -> Self::Rows<'c> {
+                ($( $fetch::rows(&mut columns.$index), )*)
+            }
+
+            fn matches(rows: Self::Rows<'_>, indices: &[usize]) -> bool {
+                $( $fetch::matches(rows.$index, indices[$index]) )&&*
+            }
+
+            unsafe fn fetch<'c>(rows: Self::Rows<'c>, indices: &[usize]) -> Self::Items<'c> {
+                ($( $fetch::fetch(rows.$index, indices[$index]), )*)
+            }
+        }
+    };
+}
+
+impl_query_data!(A 0);
+impl_query_data!(A 0, B 1);
+impl_query_data!(A 0, B 1, C 2);
+impl_query_data!(A 0, B 1, C 2, D 3);
+impl_query_data!(A 0, B 1, C 2, D 3, E 4);
+impl_query_data!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_query_data!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_query_data!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_query_data!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_query_data!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+impl_query_data!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+impl_query_data!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+impl_query_data!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12);
+impl_query_data!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13);
+impl_query_data!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13, O 14);
+impl_query_data!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13, O 14, P 15);
+
+/// The matches of a cached query, with the columns locked for as long as it's alive.
+///
+/// Components are borrowed from the query itself, so it is advanced with `while let`
+/// instead of being an [`Iterator`].
+pub struct Query<'w, C> {
+    columns: C,
+    indices: ChunksExact<'w, usize>,
+}
+
+impl<'w, C: JoinColumns> Query<'w, C> {
+    pub(super) fn new(columns: C, indices: ChunksExact<'w, usize>) -> Self {
+        Self { columns, indices }
+    }
+
+    pub fn next_match(&mut self) -> Option<C::Items<'_>> {
+        let columns = &self.columns;
+        let indices = self.indices.find(|indices| columns.matches(indices))?;
+        Some(self.columns.fetch(indices))
+    }
+}
+
+/// The matches of a cached query, split between the threads of the rayon pool, see
+/// [`World::par_query`].
+pub struct ParQuery<'w, Q: QueryData> {
+    columns: Q::Columns<'w>,
+    indices: &'w [usize],
+    width: usize,
+}
+
+impl<'w, Q: QueryData> ParQuery<'w, Q> {
+    pub(super) fn new(columns: Q::Columns<'w>, indices: &'w [usize], width: usize) -> Self {
+        Self { columns, indices, width }
+    }
+
+    pub fn for_each(&mut self, op: impl Fn(Q::Items<'_>) + Send + Sync) {
+        let rows = Q::rows(&mut self.columns);
+        self.indices.par_chunks(self.width).for_each(|indices| {
+            if !Q::matches(rows, indices) {
+                return;
+            }
+            // Every entity matches a cached query once and has at most one row per column,
+            // which are distinct columns since `QueryData::key` rejects aliasing. So no row
+            // is fetched twice.
+            op(unsafe { Q::fetch(rows, indices) })
+        });
+    }
+}
@@ -0,0 +1,64 @@
+use cgmath::*;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::EcsBenchmark;
+use super::{Id, World};
+
+#[derive(Copy, Clone)]
+pub struct Position(Vector3<f32>);
+
+#[derive(Copy, Clone)]
+pub struct Velocity(Vector3<f32>);
+
+pub struct Benchmark {
+    world: Box<World>,
+    /// The entities in the order they are looked up.
+    shuffled: Vec<Id>,
+    sum: f64,
+}
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = Box::new(World::new());
+        let mut shuffled = Vec::with_capacity(size);
+
+        {
+            let pos = world.register::<Position>().unwrap();
+            let mut pos = pos.write().unwrap();
+            let vel = world.register::<Velocity>().unwrap();
+            let mut vel = vel.write().unwrap();
+
+            for i in 0..size {
+                let mut entity = world.new_entity();
+                entity.add_component(&mut pos, Position(Vector3::new(i as f32, 0.0, 0.0)));
+                entity.add_component(&mut vel, Velocity(Vector3::unit_x()));
+                shuffled.push(entity.id);
+            }
+        }
+        shuffled.shuffle(&mut StdRng::seed_from_u64(0));
+
+        Benchmark {
+            world,
+            shuffled,
+            sum: 0.0,
+        }
+    }
+
+    fn run(&mut self) {
+        let mut sum = 0.0;
+        for id in &self.shuffled {
+            let pos = self.world.get::<Position>(id).unwrap().unwrap();
+            let vel = self.world.get::<Velocity>(id).unwrap().unwrap();
+            sum += (pos.0.x + vel.0.x) as f64;
+        }
+        self.sum = sum;
+    }
+
+    fn checksum(&mut self) -> f64 {
+        self.sum
+    }
+}
@@ -0,0 +1,148 @@
+use std::any::type_name;
+use std::fmt;
+
+use super::Id;
+
+/// Misuse of component types and columns, see [`World::register`](super::World::register).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ComponentError {
+    /// No column was registered for the type.
+    Unregistered { component: &'static str },
+    /// The type is already registered, under a different id.
+    Reregistered { component: &'static str, id: Id, requested: Id },
+    /// The id is already taken by the column of another type.
+    IdTaken { id: Id, component: &'static str, requested: &'static str },
+    /// The column stores a different type than the one it was accessed as.
+    TypeMismatch { id: Id, component: &'static str, requested: &'static str },
+    /// Hooks were given for a column that already exists.
+    HooksTooLate { component: &'static str },
+    /// The type occurs more than once in a query, bundle or tuple of resources.
+    Aliased { component: &'static str },
+    /// The vectors of a batch spawned from one vector per component differ in length, see
+    /// [`World::spawn_soa`](super::World::spawn_soa).
+    UnevenBatch,
+    /// A query or join over optional and excluded components only, see
+    /// [`QueryData::key`](super::QueryData::key).
+    NothingRequired,
+    /// A thread panicked while holding the column's lock.
+    Poisoned { id: Id, component: &'static str },
+    /// There is no entity with the id.
+    NoEntity { id: Id },
+    /// The id is already taken by another entity.
+    EntityExists { id: Id },
+    /// The parent is the entity itself or one of its descendants, see
+    /// [`World::set_parent`](super::World::set_parent).
+    Cycle { child: Id, parent: Id },
+    /// No resource of the type was inserted.
+    NoResource { resource: &'static str },
+    /// A thread panicked while holding the resource's lock.
+    ResourcePoisoned { resource: &'static str },
+    /// The snapshot has no column with the id.
+    NoColumn { id: Id },
+    /// The snapshot column was written as a type with another name or row layout.
+    LayoutMismatch { id: Id, requested: &'static str },
+    /// The column's rows are mapped from a snapshot and can't be written, see
+    /// [`World::map_snapshot`](super::World::map_snapshot).
+    ReadOnly { id: Id, component: &'static str },
+}
+
+impl fmt::Display for ComponentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ComponentError::Unregistered { component } => {
+                write!(f, "component `{}` is not registered", component)
+            }
+            ComponentError::Reregistered { component, id, requested } => write!(
+                f,
+                "component `{}` is registered as {}, not {}",
+                component,
+                Hex(id),
+                Hex(requested)
+            ),
+            ComponentError::IdTaken { id, component, requested } => write!(
+                f,
+                "can't register `{}` as {}, which is taken by `{}`",
+                requested,
+                Hex(id),
+                component
+            ),
+            ComponentError::TypeMismatch { id, component, requested } => write!(
+                f,
+                "column {} stores `{}`, not `{}`",
+                Hex(id),
+                component,
+                requested
+            ),
+            ComponentError::HooksTooLate { component } => {
+                write!(f, "component `{}` is already registered, its hooks can't be set", component)
+            }
+            ComponentError::Aliased { component } => {
+                write!(f, "`{}` occurs more than once in the query, bundle or resources", component)
+            }
+            ComponentError::UnevenBatch => write!(f, "the component vectors of the batch differ in length"),
+            ComponentError::NothingRequired => write!(f, "a query needs at least one required component"),
+            ComponentError::Poisoned { id, component } => {
+                write!(f, "lock of column {} (`{}`) is poisoned", Hex(id), component)
+            }
+            ComponentError::NoEntity { id } => write!(f, "entity {} doesn't exist", Hex(id)),
+            ComponentError::EntityExists { id } => write!(f, "entity {} already exists", Hex(id)),
+            ComponentError::Cycle { child, parent } => write!(
+                f,
+                "entity {} can't be a child of itself or its descendant {}",
+                Hex(child),
+                Hex(parent)
+            ),
+            ComponentError::NoResource { resource } => {
+                write!(f, "resource `{}` is not inserted", resource)
+            }
+            ComponentError::ResourcePoisoned { resource } => {
+                write!(f, "lock of resource `{}` is poisoned", resource)
+            }
+            ComponentError::NoColumn { id } => write!(f, "snapshot has no column {}", Hex(id)),
+            ComponentError::LayoutMismatch { id, requested } => write!(
+                f,
+                "snapshot column {} wasn't written as `{}`",
+                Hex(id),
+                requested
+            ),
+            ComponentError::ReadOnly { id, component } => {
+                write!(f, "column {} (`{}`) is mapped from a snapshot and read-only", Hex(id), component)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ComponentError {}
+
+struct Hex<'a>(&'a Id);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:02X}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// Derives the id of a component from its type name, using 128 bit FNV-1a.
+///
+/// The id is the same across runs and processes, as long as the type keeps its path and
+/// the compiler keeps formatting it the same way.
+pub fn stable_id<T: ?Sized>() -> Id {
+    fnv1a(type_name::<T>().bytes())
+}
+
+/// Derives the id of a resource from its type name like [`stable_id`], but prefixed so that
+/// it never equals the id of the column of the same type.
+pub fn resource_id<T: ?Sized>() -> Id {
+    fnv1a(b"resource ".iter().copied().chain(type_name::<T>().bytes()))
+}
+
+fn fnv1a(bytes: impl Iterator<Item = u8>) -> Id {
+    const OFFSET: u128 = 0x6c62272e07bb014262b821756295c58d;
+    const PRIME: u128 = 0x0000000001000000000000000000013b;
+
+    let hash = bytes.fold(OFFSET, |hash, byte| (hash ^ byte as u128).wrapping_mul(PRIME));
+    hash.to_be_bytes()
+}
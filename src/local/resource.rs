@@ -0,0 +1,208 @@
+use std::any::{type_name, Any};
+use std::ops::{Deref, DerefMut};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use super::{resource_id, Access, ComponentError, Id, World};
+
+/// A singleton of the world keyed by its type, locked like a [`Column`](super::Column).
+pub struct Resource {
+    /// The [`resource_id`] of the type, identifying the resource in an [`Access`].
+    id: Id,
+    /// Type name of the stored value, for error messages.
+    resource: &'static str,
+    data: Box<dyn Any + Send + Sync>,
+}
+
+pub struct ResourceReadGuard<'a, T> {
+    pub id: Id,
+    guard: RwLockReadGuard<'a, T>
+}
+
+impl<'a, T> Deref for ResourceReadGuard<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.guard.deref()
+    }
+}
+
+pub struct ResourceWriteGuard<'a, T> {
+    pub id: Id,
+    guard: RwLockWriteGuard<'a, T>
+}
+
+impl<'a, T> Deref for ResourceWriteGuard<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.guard.deref()
+    }
+}
+
+impl<'a, T> DerefMut for ResourceWriteGuard<'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.guard.deref_mut()
+    }
+}
+
+impl Resource {
+    pub fn new<T: Send + Sync + 'static>(value: T) -> Self {
+        Self {
+            id: resource_id::<T>(),
+            resource: type_name::<T>(),
+            data: Box::new(RwLock::new(value)),
+        }
+    }
+
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    fn lock<T: 'static>(&self) -> &RwLock<T> {
+        self.data.downcast_ref().expect("resources are keyed by their type")
+    }
+
+    fn poisoned(&self) -> ComponentError {
+        ComponentError::ResourcePoisoned {
+            resource: self.resource,
+        }
+    }
+
+    pub fn read<T: 'static>(&self) -> Result<ResourceReadGuard<'_, T>, ComponentError> {
+        let guard = self.lock::<T>().read().map_err(|_| self.poisoned())?;
+        Ok(ResourceReadGuard {
+            id: self.id,
+            guard
+        })
+    }
+
+    pub fn write<T: 'static>(&self) -> Result<ResourceWriteGuard<'_, T>, ComponentError> {
+        let guard = self.lock::<T>().write().map_err(|_| self.poisoned())?;
+        Ok(ResourceWriteGuard {
+            id: self.id,
+            guard
+        })
+    }
+
+    /// The value without locking, which the exclusive borrow makes unnecessary.
+    pub fn get_mut<T: 'static>(&mut self) -> &mut T {
+        let lock: &mut RwLock<T> = self.data.downcast_mut().expect("resources are keyed by their type");
+        lock.get_mut().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Shared or exclusive access to a resource, the building block of [`ResourceData`].
+pub trait ResourceFetch {
+    type Guard<'w>;
+
+    /// Whether the resource is locked for writing, see [`Access`].
+    const WRITE: bool;
+
+    fn id() -> Id;
+
+    /// Type name of the resource, for error messages.
+    fn resource() -> &'static str;
+
+    fn lock(world: &World) -> Result<Self::Guard<'_>, ComponentError>;
+}
+
+impl<T: Send + Sync + 'static> ResourceFetch for &T {
+    type Guard<'w> = ResourceReadGuard<'w, T>;
+
+    const WRITE: bool = false;
+
+    fn id() -> Id {
+        resource_id::<T>()
+    }
+
+    fn resource() -> &'static str {
+        type_name::<T>()
+    }
+
+    fn lock(world: &World) -> Result<Self::Guard<'_>, ComponentError> {
+        world.resource::<T>()
+    }
+}
+
+impl<T: Send + Sync + 'static> ResourceFetch for &mut T {
+    type Guard<'w> = ResourceWriteGuard<'w, T>;
+
+    const WRITE: bool = true;
+
+    fn id() -> Id {
+        resource_id::<T>()
+    }
+
+    fn resource() -> &'static str {
+        type_name::<T>()
+    }
+
+    fn lock(world: &World) -> Result<Self::Guard<'_>, ComponentError> {
+        world.resource_mut::<T>()
+    }
+}
+
+/// A tuple of [`ResourceFetch`]es, e.g. `(&DeltaTime,)`, or `()` for none, see
+/// [`System`](super::System).
+pub trait ResourceData {
+    type Guards<'w>;
+
+    /// Adds the resources read and written to `access`.
+    fn access(access: &mut Access);
+
+    /// Checks that each resource occurs only once.
+    fn check() -> Result<(), ComponentError>;
+
+    /// Locks the resources, which may each occur only once.
+    fn lock(world: &World) -> Result<Self::Guards<'_>, ComponentError>;
+}
+
+impl ResourceData for () {
+    type Guards<'w> = ();
+
+    fn access(_access: &mut Access) {}
+
+    fn check() -> Result<(), ComponentError> {
+        Ok(())
+    }
+
+    fn lock(_world: &World) -> Result<Self::Guards<'_>, ComponentError> {
+        Ok(())
+    }
+}
+
+macro_rules! impl_resource_data {
+    ($( $fetch:ident ),*) => {
+        impl<$( $fetch: ResourceFetch ),*> ResourceData for ($( $fetch, )*) {
+            type Guards<'w> = ($( $fetch::Guard<'w>, )*);
+
+            fn access(access: &mut Access) {
+                $( access.declare($fetch::id(), $fetch::WRITE); )*
+            }
+
+            fn check() -> Result<(), ComponentError> {
+                let ids = [$( ($fetch::id(), $fetch::resource()) ),*];
+                for (index, (id, resource)) in ids.iter().enumerate() {
+                    if ids[..index].iter().any(|(other, _)| other == id) {
+                        return Err(ComponentError::Aliased { component: *resource });
+                    }
+                }
+                Ok(())
+            }
+
+            fn lock(world: &World) -> Result<Self::Guards<'_>, ComponentError> {
+                Self::check()?;
+                Ok(($( $fetch::lock(world)?, )*))
+            }
+        }
+    };
+}
+
+impl_resource_data!(A);
+impl_resource_data!(A, B);
+impl_resource_data!(A, B, C);
+impl_resource_data!(A, B, C, D);
+impl_resource_data!(A, B, C, D, E);
+impl_resource_data!(A, B, C, D, E, F);
+impl_resource_data!(A, B, C, D, E, F, G);
+impl_resource_data!(A, B, C, D, E, F, G, H);
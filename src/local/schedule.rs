@@ -0,0 +1,124 @@
+use crate::EcsBenchmark;
+use super::{Column, ColumnWriteGuard, Query, QueryData, Schedule, System, World};
+
+pub struct A(f32);
+pub struct B(f32);
+pub struct C(f32);
+pub struct D(f32);
+pub struct E(f32);
+
+struct AB;
+
+impl System for AB {
+    type Query = (&'static mut A, &'static mut B);
+    type Resources = ();
+
+    fn run(&mut self, mut query: Query<'_, <Self::Query as QueryData>::Columns<'_>>, _: ()) {
+        while let Some((a, b)) = query.next_match() {
+            std::mem::swap(&mut a.0, &mut b.0);
+        }
+    }
+}
+
+struct CD;
+
+impl System for CD {
+    type Query = (&'static mut C, &'static mut D);
+    type Resources = ();
+
+    fn run(&mut self, mut query: Query<'_, <Self::Query as QueryData>::Columns<'_>>, _: ()) {
+        while let Some((c, d)) = query.next_match() {
+            std::mem::swap(&mut c.0, &mut d.0);
+        }
+    }
+}
+
+struct CE;
+
+impl System for CE {
+    type Query = (&'static mut C, &'static mut E);
+    type Resources = ();
+
+    fn run(&mut self, mut query: Query<'_, <Self::Query as QueryData>::Columns<'_>>, _: ()) {
+        while let Some((c, e)) = query.next_match() {
+            std::mem::swap(&mut c.0, &mut e.0);
+        }
+    }
+}
+
+pub struct Benchmark {
+    world: Box<World>,
+    schedule: Schedule,
+}
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = Box::new(World::new());
+
+        let a = world.register::<A>().unwrap();
+        let b = world.register::<B>().unwrap();
+        let c = world.register::<C>().unwrap();
+        let d = world.register::<D>().unwrap();
+        let e = world.register::<E>().unwrap();
+
+        {
+            let mut a: ColumnWriteGuard<'_, A> = a.write().unwrap();
+            let mut b: ColumnWriteGuard<'_, B> = b.write().unwrap();
+            let mut c: ColumnWriteGuard<'_, C> = c.write().unwrap();
+            let mut d: ColumnWriteGuard<'_, D> = d.write().unwrap();
+            let mut e: ColumnWriteGuard<'_, E> = e.write().unwrap();
+
+            for kind in 0..4 {
+                for _ in 0..size {
+                    let mut entity = world.new_entity();
+                    entity.add_component(&mut a, A(1.0));
+                    entity.add_component(&mut b, B(2.0));
+                    match kind {
+                        0 => {}
+                        1 => {
+                            entity.add_component(&mut c, C(3.0));
+                        }
+                        2 => {
+                            entity.add_component(&mut c, C(3.0));
+                            entity.add_component(&mut d, D(4.0));
+                        }
+                        _ => {
+                            entity.add_component(&mut c, C(3.0));
+                            entity.add_component(&mut e, E(5.0));
+                        }
+                    }
+                }
+            }
+        }
+
+        // `CD` and `CE` both write `C`, so they end up in separate batches.
+        let mut schedule = Schedule::new();
+        schedule.add_system(&mut world, AB).unwrap();
+        schedule.add_system(&mut world, CD).unwrap();
+        schedule.add_system(&mut world, CE).unwrap();
+
+        Benchmark { world, schedule }
+    }
+
+    fn run(&mut self) {
+        self.schedule.run(&self.world).unwrap();
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let b: f32 = sum::<B>(&self.world, |b| b.0);
+        let c: f32 = sum::<C>(&self.world, |c| c.0);
+        let d: f32 = sum::<D>(&self.world, |d| d.0);
+        let e: f32 = sum::<E>(&self.world, |e| e.0);
+        (b + c + d + e) as f64
+    }
+}
+
+fn sum<T: Send + Sync + 'static>(world: &World, value: impl Fn(&T) -> f32) -> f32 {
+    let column: &Column = world.column::<T>().unwrap();
+    let rows = column.read::<T>().unwrap();
+    rows.iter().map(|row| value(&row.inner)).sum()
+}
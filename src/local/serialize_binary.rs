@@ -0,0 +1,225 @@
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::{env, process};
+
+use bincode::Options;
+use memmap2::MmapMut;
+use serde::{Deserialize, Serialize};
+
+use crate::EcsBenchmark;
+
+use super::{Plain, QueryData, Snapshot, World};
+
+#[derive(Default, Copy, Clone, Serialize, Deserialize)]
+pub struct Transform([f32; 16]);
+
+#[derive(Default, Copy, Clone, Serialize, Deserialize)]
+pub struct Position {
+    x: f32,
+    y: f32,
+    z: f32,
+}
+
+#[derive(Default, Copy, Clone, Serialize, Deserialize)]
+pub struct Rotation {
+    x: f32,
+    y: f32,
+    z: f32,
+}
+
+#[derive(Default, Copy, Clone, Serialize, Deserialize)]
+pub struct Velocity {
+    x: f32,
+    y: f32,
+    z: f32,
+}
+
+// SAFETY: the components are `f32`s without padding between them.
+unsafe impl Plain for Transform {}
+unsafe impl Plain for Position {}
+unsafe impl Plain for Rotation {}
+unsafe impl Plain for Velocity {}
+
+/// The component types saved and loaded, see [`World::save`].
+type Saved = (Transform, Position, Rotation, Velocity);
+
+/// The query cached by [`populate`], which has to be rebuilt on load.
+type Cached = (&'static Position, &'static Velocity);
+
+/// Fixed size integers, like `bincode::serialize` as used by the other backends.
+fn options() -> impl Options {
+    bincode::DefaultOptions::new().with_fixint_encoding().allow_trailing_bytes()
+}
+
+pub struct Benchmark {
+    world: Box<World>,
+    restored: Option<Box<World>>,
+}
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        Benchmark {
+            world: populate(size),
+            restored: None,
+        }
+    }
+
+    fn run(&mut self) {
+        let mut encoded = Vec::new();
+        self.world
+            .save::<Saved>()
+            .serialize(&mut bincode::Serializer::new(&mut encoded, options()))
+            .unwrap();
+        let restored = World::load::<Saved, _>(&mut bincode::Deserializer::from_slice(&encoded, options())).unwrap();
+        self.restored = Some(Box::new(restored));
+    }
+
+    fn checksum(&mut self) -> f64 {
+        checksum(self.restored.as_mut().unwrap())
+    }
+
+    fn teardown(&mut self) {
+        self.restored = None;
+    }
+}
+
+/// The same world as [`Benchmark`], written as a snapshot into an anonymous memory map and
+/// mapped back as a world from it, instead of going through bincode.
+pub struct SnapshotBenchmark {
+    world: Box<World>,
+    /// Length of the world's snapshot, which the map is created with.
+    len: usize,
+    restored: Option<Box<World>>,
+}
+
+impl EcsBenchmark for SnapshotBenchmark {
+    fn name() -> &'static str {
+        super::SNAPSHOT_NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let world = populate(size);
+        let mut written = Vec::new();
+        world.snapshot::<Saved>(&mut written).unwrap();
+
+        SnapshotBenchmark {
+            world,
+            len: written.len(),
+            restored: None,
+        }
+    }
+
+    fn run(&mut self) {
+        let mut map = MmapMut::map_anon(self.len).unwrap();
+        self.world.snapshot::<Saved>(&mut map[..]).unwrap();
+
+        self.restored = Some(restore(Snapshot::from_map(map).unwrap()));
+    }
+
+    fn checksum(&mut self) -> f64 {
+        checksum(self.restored.as_mut().unwrap())
+    }
+
+    fn teardown(&mut self) {
+        self.restored = None;
+    }
+}
+
+/// The same as [`SnapshotBenchmark`], but writing the snapshot to a file and mapping it
+/// back through [`Snapshot::open`].
+pub struct FileSnapshotBenchmark {
+    world: Box<World>,
+    path: PathBuf,
+    restored: Option<Box<World>>,
+}
+
+impl EcsBenchmark for FileSnapshotBenchmark {
+    fn name() -> &'static str {
+        super::SNAPSHOT_FILE_NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let file = format!("ecs_bench_suite-{}-{}.snapshot", process::id(), size);
+
+        FileSnapshotBenchmark {
+            world: populate(size),
+            path: env::temp_dir().join(file),
+            restored: None,
+        }
+    }
+
+    fn run(&mut self) {
+        // The file is rewritten, so the previous run's mapping of it has to go first.
+        self.restored = None;
+
+        let mut file = BufWriter::new(File::create(&self.path).unwrap());
+        self.world.snapshot::<Saved>(&mut file).unwrap();
+        drop(file);
+
+        // SAFETY: the file is only rewritten by the next run, after the world mapping it
+        // was dropped.
+        let snapshot = unsafe { Snapshot::open(&self.path) }.unwrap();
+        self.restored = Some(restore(snapshot));
+    }
+
+    fn checksum(&mut self) -> f64 {
+        checksum(self.restored.as_mut().unwrap())
+    }
+
+    fn teardown(&mut self) {
+        self.restored = None;
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Maps the snapshot back as a world and rebuilds its cached query, which snapshots don't
+/// store.
+fn restore(snapshot: Snapshot) -> Box<World> {
+    let mut world = World::map_snapshot::<Saved>(snapshot).unwrap();
+    world.query::<Cached>().unwrap();
+    Box::new(world)
+}
+
+pub(super) fn populate(size: usize) -> Box<World> {
+    let mut world = Box::new(World::new());
+
+    {
+        let tf = world.register::<Transform>().unwrap();
+        let mut tf = tf.write().unwrap();
+        let pos = world.register::<Position>().unwrap();
+        let mut pos = pos.write().unwrap();
+        let rot = world.register::<Rotation>().unwrap();
+        let mut rot = rot.write().unwrap();
+        let vel = world.register::<Velocity>().unwrap();
+        let mut vel = vel.write().unwrap();
+
+        for i in 0..size {
+            let mut entity = world.new_entity();
+            entity.add_component(&mut tf, Transform::default());
+            entity.add_component(&mut pos, Position { x: i as f32, y: 0.0, z: 0.0 });
+            entity.add_component(&mut rot, Rotation::default());
+            entity.add_component(&mut vel, Velocity { x: 1.0, y: 0.0, z: 0.0 });
+        }
+    }
+    world.query::<Cached>().unwrap();
+
+    world
+}
+
+/// Sums the positions and velocities of a loaded world through its restored cached query.
+pub(super) fn checksum(world: &mut World) -> f64 {
+    let key = Cached::key(world).unwrap();
+    assert!(world.queries.contains_key(&key), "the cached query wasn't restored");
+
+    let mut query = world.query::<Cached>().unwrap();
+    let mut sum = 0.0;
+    while let Some((pos, vel)) = query.next_match() {
+        sum += (pos.x + vel.x) as f64;
+    }
+    sum
+}
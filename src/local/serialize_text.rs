@@ -0,0 +1,40 @@
+use crate::EcsBenchmark;
+use super::serialize_binary::{checksum, populate, Position, Rotation, Transform, Velocity};
+use super::World;
+
+/// The component types saved and loaded, see [`World::save`].
+type Saved = (Transform, Position, Rotation, Velocity);
+
+/// The same world as [`serialize_binary::Benchmark`](super::serialize_binary::Benchmark),
+/// saved to and loaded from RON.
+pub struct Benchmark {
+    world: Box<World>,
+    restored: Option<Box<World>>,
+}
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        Benchmark {
+            world: populate(size),
+            restored: None,
+        }
+    }
+
+    fn run(&mut self) {
+        let encoded = ron::ser::to_string(&self.world.save::<Saved>()).unwrap();
+        let restored = World::load::<Saved, _>(&mut ron::de::Deserializer::from_str(&encoded).unwrap()).unwrap();
+        self.restored = Some(Box::new(restored));
+    }
+
+    fn checksum(&mut self) -> f64 {
+        checksum(self.restored.as_mut().unwrap())
+    }
+
+    fn teardown(&mut self) {
+        self.restored = None;
+    }
+}
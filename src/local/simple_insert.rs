@@ -1,8 +1,10 @@
-use arrayvec::ArrayVec;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
 use cgmath::*;
-use tribles::fucid;
 
-use super::World;
+use crate::EcsBenchmark;
+use super::{stable_id, Hooks, Id, Ranges, Sequential, World};
 
 #[derive(Copy, Clone)]
 pub struct Transform(Matrix4<f32>);
@@ -20,37 +22,305 @@ pub struct Query {
 }
 
 pub struct Benchmark {
+    size: usize,
+    world: Option<Box<World>>
+}
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        Benchmark { size, world: None }
+    }
+
+    fn run(&mut self) {
+        self.world = Some(insert(World::new(), self.size));
+    }
+
+    fn checksum(&mut self) -> f64 {
+        checksum(self.world.as_ref().unwrap())
+    }
+
+    fn teardown(&mut self) {
+        self.world = None;
+    }
+}
+
+/// Ids reserved at a time by the allocator of [`RangesBenchmark`].
+const RANGE: u64 = 4096;
+
+/// The same inserts as [`Benchmark`], with ids counting up instead of [`fucid`](super::Fucid)s.
+pub struct SequentialBenchmark {
+    size: usize,
+    world: Option<Box<World>>
+}
+
+impl EcsBenchmark for SequentialBenchmark {
+    fn name() -> &'static str {
+        super::SEQUENTIAL_NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        SequentialBenchmark { size, world: None }
+    }
+
+    fn run(&mut self) {
+        self.world = Some(insert(World::with_ids(Sequential::new()), self.size));
+    }
+
+    fn checksum(&mut self) -> f64 {
+        checksum(self.world.as_ref().unwrap())
+    }
+
+    fn teardown(&mut self) {
+        self.world = None;
+    }
+}
+
+/// The same inserts as [`Benchmark`], with ids from ranges of [`RANGE`] reserved from a
+/// shared counter.
+pub struct RangesBenchmark {
+    size: usize,
+    /// The allocator every run's world shares its counter with.
+    ids: Ranges,
+    world: Option<Box<World>>
+}
+
+impl EcsBenchmark for RangesBenchmark {
+    fn name() -> &'static str {
+        super::RANGES_NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        RangesBenchmark { size, ids: Ranges::new(RANGE), world: None }
+    }
+
+    fn run(&mut self) {
+        self.world = Some(insert(World::with_ids(self.ids.share()), self.size));
+    }
+
+    fn checksum(&mut self) -> f64 {
+        checksum(self.world.as_ref().unwrap())
+    }
+
+    fn teardown(&mut self) {
+        self.world = None;
+    }
+}
+
+/// Spawns `size` entities into the world one by one, adding their components one at a
+/// time.
+fn insert(world: World, size: usize) -> Box<World> {
+    let mut world = Box::new(world);
+
+    let tf = world.register::<Transform>().unwrap();
+    let mut tf = tf.write().unwrap();
+    let pos = world.register::<Position>().unwrap();
+    let mut pos = pos.write().unwrap();
+    let rot = world.register::<Rotation>().unwrap();
+    let mut rot = rot.write().unwrap();
+    let vel = world.register::<Velocity>().unwrap();
+    let mut vel = vel.write().unwrap();
+
+    for _ in 0..size {
+        let mut entity = world.new_entity();
+        entity.add_component(&mut tf, Transform(Matrix4::<f32>::from_scale(1.0)));
+        entity.add_component(&mut pos, Position(Vector3::unit_x()));
+        entity.add_component(&mut rot, Rotation(Vector3::unit_x()));
+        entity.add_component(&mut vel, Velocity(Vector3::unit_x()));
+    }
+
+    world
+}
+
+fn checksum(world: &World) -> f64 {
+    let pos = world.column::<Position>().unwrap();
+    let pos: super::ColumnReadGuard<'_, Position> = pos.read().unwrap();
+    pos.iter().map(|row| row.inner.0.x as f64).sum()
+}
+
+/// The same inserts as [`Benchmark`], spawning all entities through
+/// [`World::spawn_batch`].
+pub struct SpawnBatchBenchmark {
+    size: usize,
+    world: Option<Box<World>>
+}
+
+impl EcsBenchmark for SpawnBatchBenchmark {
+    fn name() -> &'static str {
+        super::SPAWN_BATCH_NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        SpawnBatchBenchmark { size, world: None }
+    }
+
+    fn run(&mut self) {
+        let mut world = Box::new(World::new());
+
+        world
+            .spawn_batch((0..self.size).map(|_| {
+                (
+                    Transform(Matrix4::<f32>::from_scale(1.0)),
+                    Position(Vector3::unit_x()),
+                    Rotation(Vector3::unit_x()),
+                    Velocity(Vector3::unit_x()),
+                )
+            }))
+            .unwrap();
+
+        self.world = Some(world);
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let world = self.world.as_ref().unwrap();
+        let pos = world.column::<Position>().unwrap();
+        let pos: super::ColumnReadGuard<'_, Position> = pos.read().unwrap();
+        pos.iter().map(|row| row.inner.0.x as f64).sum()
+    }
+
+    fn teardown(&mut self) {
+        self.world = None;
+    }
 }
 
-impl Benchmark {
-    pub fn new() -> Self {
-        Benchmark {}
+/// The same inserts as [`Benchmark`], spawning all entities through [`World::spawn_soa`]
+/// from one vector per component, like legion's `into_soa`.
+pub struct SoaBenchmark {
+    size: usize,
+    world: Option<Box<World>>
+}
+
+impl EcsBenchmark for SoaBenchmark {
+    fn name() -> &'static str {
+        super::SOA_NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        SoaBenchmark { size, world: None }
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         let mut world = Box::new(World::new());
 
-        let tf = world.new_component::<Transform>(fucid());
-        let mut tf = tf.write().unwrap();
-        let pos = world.new_component::<Position>(fucid());
-        let mut pos = pos.write().unwrap();
-        let rot = world.new_component::<Rotation>(fucid());
-        let mut rot = rot.write().unwrap();
-        let vel = world.new_component::<Velocity>(fucid());
-        let mut vel = vel.write().unwrap();
-        
-        for _ in 0..1_000_000 {
-            let entity = world.new_entity();
-            entity.add_component(&mut tf, Transform(Matrix4::<f32>::from_scale(1.0)));
-            entity.add_component(&mut pos, Position(Vector3::unit_x()));
-            entity.add_component(&mut rot, Rotation(Vector3::unit_x()));
-            entity.add_component(&mut vel, Velocity(Vector3::unit_x()));
+        world
+            .spawn_soa::<(Transform, Position, Rotation, Velocity)>((
+                vec![Transform(Matrix4::<f32>::from_scale(1.0)); self.size],
+                vec![Position(Vector3::unit_x()); self.size],
+                vec![Rotation(Vector3::unit_x()); self.size],
+                vec![Velocity(Vector3::unit_x()); self.size],
+            ))
+            .unwrap();
+
+        self.world = Some(world);
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let world = self.world.as_ref().unwrap();
+        let pos = world.column::<Position>().unwrap();
+        let pos: super::ColumnReadGuard<'_, Position> = pos.read().unwrap();
+        pos.iter().map(|row| row.inner.0.x as f64).sum()
+    }
+
+    fn teardown(&mut self) {
+        self.world = None;
+    }
+}
+
+/// Entities per cell of a coarse grid over the x axis, kept up to date by the hooks of
+/// the `Position` column.
+struct SpatialIndex {
+    cells: Vec<AtomicU32>,
+}
+
+impl SpatialIndex {
+    /// The index of the cell the position falls into.
+    fn cell_of(&self, pos: &Position) -> usize {
+        (pos.0.x.floor() as i64).rem_euclid(self.cells.len() as i64) as usize
+    }
+
+    fn cell(&self, pos: &Position) -> &AtomicU32 {
+        &self.cells[self.cell_of(pos)]
+    }
+}
+
+/// The same inserts as [`Benchmark`], with a `Position` column whose hooks maintain a
+/// [`SpatialIndex`].
+pub struct HooksBenchmark {
+    size: usize,
+    world: Option<Box<World>>,
+    index: Arc<SpatialIndex>,
+}
+
+impl HooksBenchmark {
+    /// Hooks counting every position into its cell of the index.
+    fn hooks(&self) -> Hooks<Position> {
+        let (added, replaced, removed) = (self.index.clone(), self.index.clone(), self.index.clone());
+        Hooks::new()
+            .on_add(move |_, pos: &Position| {
+                added.cell(pos).fetch_add(1, Ordering::Relaxed);
+            })
+            .on_replace(move |_, previous: &Position, pos: &Position| {
+                replaced.cell(previous).fetch_sub(1, Ordering::Relaxed);
+                replaced.cell(pos).fetch_add(1, Ordering::Relaxed);
+            })
+            .on_remove(move |_, pos: &Position| {
+                removed.cell(pos).fetch_sub(1, Ordering::Relaxed);
+            })
+    }
+}
+
+impl EcsBenchmark for HooksBenchmark {
+    fn name() -> &'static str {
+        super::HOOKS_NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let cells = (0..64).map(|_| AtomicU32::new(0)).collect();
+        HooksBenchmark { size, world: None, index: Arc::new(SpatialIndex { cells }) }
+    }
+
+    fn run(&mut self) {
+        for cell in &self.index.cells {
+            cell.store(0, Ordering::Relaxed);
+        }
+
+        let mut world = World::new();
+        world.new_component_with_hooks(stable_id::<Position>(), self.hooks()).unwrap();
+        self.world = Some(insert(world, self.size));
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let world = self.world.as_mut().unwrap();
+
+        // Moves the first position from x = 1 to 2 and despawns the second entity, at x = 1,
+        // outside of the timed section, so that the replace and remove hooks run as well
+        // without changing the sum of the positions.
+        let ids: Vec<Id> = world.entities.iter().take(2).map(|entity| entity.id).collect();
+        if let [first, second] = ids[..] {
+            let pos = world.register::<Position>().unwrap();
+            let mut pos = pos.write().unwrap();
+            let mut entity = world.entity_mut(&first).unwrap();
+            entity.add_component(&mut pos, Position(Vector3::new(2.0, 0.0, 0.0)));
+            drop(pos);
+
+            world.despawn(&second);
         }
 
-        //let mut query = ArrayVec::new();
-        //query.push(pos.id);
-        //query.push(vel.id);
-        
-        //world.new_query(&query);
+        let pos = world.column::<Position>().unwrap();
+        let pos: super::ColumnReadGuard<'_, Position> = pos.read().unwrap();
+        let mut expected = vec![0; self.index.cells.len()];
+        for row in pos.iter() {
+            expected[self.index.cell_of(&row.inner)] += 1;
+        }
+        let indexed: Vec<u32> = self.index.cells.iter().map(|cell| cell.load(Ordering::Relaxed)).collect();
+        assert_eq!(indexed, expected, "the spatial index is out of date");
+        pos.iter().map(|row| row.inner.0.x as f64).sum()
+    }
+
+    fn teardown(&mut self) {
+        self.world = None;
     }
 }
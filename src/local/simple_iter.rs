@@ -1,11 +1,8 @@
-use std::u32;
-
-use arrayvec::ArrayVec;
 use cgmath::*;
-use rand::Rng;
-use tribles::{fucid, genid, namespace::hex_literal::hex};
+use tribles::namespace::hex_literal::hex;
 
-use super::{Id, World};
+use crate::EcsBenchmark;
+use super::{Id, Query as CachedQuery, QueryData, QueryKey, ResourceReadGuard, Schedule, Sequential, System, Term, World};
 
 #[derive(Copy, Clone)]
 pub struct Transform(Matrix4<f32>);
@@ -29,45 +26,63 @@ pub struct Benchmark {
 const POS: Id = hex!("09D8E7A7E0A8B00C9E9823110D2842B6");
 const VEL: Id = hex!("1FCC336CE90B1D9472A9B734586CA6AF");
 
-impl Benchmark {
-    pub fn new() -> Self {
-        let mut rng = rand::thread_rng();
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
 
-        let mut world = Box::new(World::new());
+    fn setup(size: usize) -> Self {
+        let mut world = populate(World::new(), size);
 
-        let tf = world.new_component::<Transform>(fucid());
-        let mut tf = tf.write().unwrap();
-        let pos = world.new_component::<Position>(POS);
-        let mut pos = pos.write().unwrap();
-        let rot = world.new_component::<Rotation>(fucid());
-        let mut rot = rot.write().unwrap();
-        let vel = world.new_component::<Velocity>(VEL);
-        let mut vel = vel.write().unwrap();
-        
-        for _ in 0..1_000_000 {
-            let entity = world.new_entity();
-                entity.add_component(&mut tf, Transform(Matrix4::<f32>::from_scale(1.0)));
-                entity.add_component(&mut pos, Position(Vector3::unit_x()));
-                entity.add_component(&mut rot, Rotation(Vector3::unit_x()));
-                entity.add_component(&mut vel, Velocity(Vector3::unit_x()));
+        // Builds the cached query outside of the timed section.
+        world.query::<(&mut Position, &Velocity)>().unwrap();
+
+        Benchmark {
+            world,
         }
+    }
 
+    fn run(&mut self) {
+        let mut query = self.world.query::<(&mut Position, &Velocity)>().unwrap();
+        while let Some((pos, vel)) = query.next_match() {
+            pos.0 += vel.0;
+        }
+    }
 
-        let mut query = ArrayVec::new();
-        query.push(POS);
-        query.push(VEL);
+    fn checksum(&mut self) -> f64 {
+        checksum(&self.world)
+    }
+}
+
+/// The same iteration as [`Benchmark`], but fetching the guards by column id and
+/// indexing them with the cached row indices without bounds checks.
+pub struct UncheckedBenchmark {
+    world: Box<World>
+}
+
+impl EcsBenchmark for UncheckedBenchmark {
+    fn name() -> &'static str {
+        super::UNCHECKED_NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = populate(World::new(), size);
+
+        let mut query = QueryKey::new();
+        query.push(Term::required(POS));
+        query.push(Term::required(VEL));
         
         world.new_query(&query);
 
-        Benchmark {
+        UncheckedBenchmark {
             world,
         }
     }
 
-    pub fn run(&mut self) {
-        let mut query = ArrayVec::new();
-        query.push(POS);
-        query.push(VEL);
+    fn run(&mut self) {
+        let mut query = QueryKey::new();
+        query.push(Term::required(POS));
+        query.push(Term::required(VEL));
 
         let pos = self.world.components.get(&POS).unwrap().clone();
         let mut pos: super::ColumnWriteGuard<'_, Position> = pos.write().unwrap();
@@ -75,10 +90,162 @@ impl Benchmark {
         let vel: super::ColumnReadGuard<'_, Velocity> = vel.read().unwrap();
 
 
-        for q in self.world.query(&query).unwrap() {
+        for q in self.world.query_indices(&query).unwrap() {
             unsafe {
-                pos.get_unchecked_mut(*q.get_unchecked(0)).inner.0 += vel.get_unchecked(*q.get_unchecked(1)).inner.0;
+                pos.modify_unchecked(*q.get_unchecked(0)).0 += vel.get_unchecked(*q.get_unchecked(1)).inner.0;
             }
         }
     }
+
+    fn checksum(&mut self) -> f64 {
+        checksum(&self.world)
+    }
+}
+
+/// The same iteration as [`Benchmark`], but as a merge join over the sorted columns
+/// instead of going through the cached query.
+pub struct JoinBenchmark {
+    world: Box<World>
+}
+
+impl EcsBenchmark for JoinBenchmark {
+    fn name() -> &'static str {
+        super::JOIN_NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = populate(World::new(), size);
+        world.sort_columns();
+
+        JoinBenchmark {
+            world,
+        }
+    }
+
+    fn run(&mut self) {
+        let mut joined = self.world.join::<(&mut Position, &Velocity)>().unwrap();
+        while let Some((pos, vel)) = joined.next_match() {
+            pos.0 += vel.0;
+        }
+    }
+
+    fn checksum(&mut self) -> f64 {
+        checksum(&self.world)
+    }
+}
+
+/// The same iteration as [`JoinBenchmark`], over entities with sequential ids, whose
+/// columns are sorted from the start.
+pub struct SequentialJoinBenchmark {
+    world: Box<World>
+}
+
+impl EcsBenchmark for SequentialJoinBenchmark {
+    fn name() -> &'static str {
+        super::SEQUENTIAL_JOIN_NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let world = populate(World::with_ids(Sequential::new()), size);
+        assert!(world.unsorted.is_empty(), "sequential ids should keep the columns sorted");
+
+        SequentialJoinBenchmark {
+            world,
+        }
+    }
+
+    fn run(&mut self) {
+        let mut joined = self.world.join::<(&mut Position, &Velocity)>().unwrap();
+        while let Some((pos, vel)) = joined.next_match() {
+            pos.0 += vel.0;
+        }
+    }
+
+    fn checksum(&mut self) -> f64 {
+        checksum(&self.world)
+    }
+}
+
+/// Time step of [`Integrate`], which is one so that the positions end up the same as for
+/// [`Benchmark`].
+pub struct DeltaTime(f32);
+
+struct Integrate;
+
+impl System for Integrate {
+    type Query = (&'static mut Position, &'static Velocity);
+    type Resources = (&'static DeltaTime,);
+
+    fn run(
+        &mut self,
+        mut query: CachedQuery<'_, <Self::Query as QueryData>::Columns<'_>>,
+        (dt,): (ResourceReadGuard<'_, DeltaTime>,),
+    ) {
+        while let Some((pos, vel)) = query.next_match() {
+            pos.0 += vel.0 * dt.0;
+        }
+    }
+}
+
+/// The same iteration as [`Benchmark`], but as a system integrating the velocities over
+/// a [`DeltaTime`] resource.
+pub struct SystemBenchmark {
+    world: Box<World>,
+    schedule: Schedule,
+}
+
+impl EcsBenchmark for SystemBenchmark {
+    fn name() -> &'static str {
+        super::SYSTEM_NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = populate(World::new(), size);
+        world.insert_resource(DeltaTime(1.0));
+
+        let mut schedule = Schedule::new();
+        schedule.add_system(&mut world, Integrate).unwrap();
+
+        SystemBenchmark {
+            world,
+            schedule,
+        }
+    }
+
+    fn run(&mut self) {
+        self.schedule.run(&self.world).unwrap();
+    }
+
+    fn checksum(&mut self) -> f64 {
+        checksum(&self.world)
+    }
+}
+
+fn populate(world: World, size: usize) -> Box<World> {
+    let mut world = Box::new(world);
+
+    let tf = world.register::<Transform>().unwrap();
+    let mut tf = tf.write().unwrap();
+    let pos = world.new_component::<Position>(POS).unwrap();
+    let mut pos = pos.write().unwrap();
+    let rot = world.register::<Rotation>().unwrap();
+    let mut rot = rot.write().unwrap();
+    let vel = world.new_component::<Velocity>(VEL).unwrap();
+    let mut vel = vel.write().unwrap();
+
+    for _ in 0..size {
+        let mut entity = world.new_entity();
+        entity.add_component(&mut tf, Transform(Matrix4::<f32>::from_scale(1.0)));
+        entity.add_component(&mut pos, Position(Vector3::unit_x()));
+        entity.add_component(&mut rot, Rotation(Vector3::unit_x()));
+        entity.add_component(&mut vel, Velocity(Vector3::unit_x()));
+    }
+
+    world
+}
+
+fn checksum(world: &World) -> f64 {
+    let pos = world.components.get(&POS).unwrap();
+    let pos: super::ColumnReadGuard<'_, Position> = pos.read().unwrap();
+    pos.iter().map(|row| row.inner.0.x as f64).sum()
 }
@@ -0,0 +1,427 @@
+use std::any::{type_name, Any};
+use std::convert::TryInto;
+use std::fs::File;
+use std::io::{self, Write};
+use std::marker::PhantomData;
+use std::mem::{align_of, size_of, size_of_val};
+use std::path::Path;
+use std::slice;
+use std::sync::{atomic, Arc};
+
+use memmap2::{Mmap, MmapMut};
+
+use super::{Column, ColumnData, ComponentError, Hooks, Id, Row, Tick, World};
+
+/// Components whose rows are plain bytes, so that columns of them can be written to a
+/// [`Snapshot`] and mapped back as they are.
+///
+/// # Safety
+///
+/// The type must be `Copy`, contain no padding, pointers or references, and be valid for
+/// any bit pattern, like the primitive numbers and arrays of them.
+pub unsafe trait Plain: Copy + Send + Sync + 'static {}
+
+macro_rules! impl_plain {
+    ($( $primitive:ty ),*) => {
+        $( unsafe impl Plain for $primitive {} )*
+    };
+}
+
+impl_plain!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+unsafe impl<T: Plain, const N: usize> Plain for [T; N] {}
+
+/// A tuple of the [`Plain`] component types written to a snapshot, see [`World::snapshot`].
+pub trait PlainComponents {
+    /// Writes the rows of the column at the end of `out`, if it stores one of the types.
+    fn write_column<W: Write>(column: &Column, out: &mut Blobs<W>) -> io::Result<()>;
+
+    /// Adds the snapshot's column `id` to the world as mapped rows, if it was written as
+    /// one of the types.
+    fn map_column(world: &mut World, snapshot: &Arc<Snapshot>, id: Id) -> Result<(), ComponentError>;
+}
+
+macro_rules! impl_plain_components {
+    ($( $component:ident ),*) => {
+        impl<$( $component: Plain ),*> PlainComponents for ($( $component, )*) {
+            fn write_column<W: Write>(column: &Column, out: &mut Blobs<W>) -> io::Result<()> {
+                $(
+                    if column.stores::<$component>() {
+                        let rows = column.read::<$component>().map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+                        return out.column(column.id, &rows);
+                    }
+                )*
+                Ok(())
+            }
+
+            fn map_column(world: &mut World, snapshot: &Arc<Snapshot>, id: Id) -> Result<(), ComponentError> {
+                $(
+                    if snapshot.column::<$component>(id).is_ok() {
+                        return map_rows::<$component>(world, snapshot, id);
+                    }
+                )*
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_plain_components!(A);
+impl_plain_components!(A, B);
+impl_plain_components!(A, B, C);
+impl_plain_components!(A, B, C, D);
+impl_plain_components!(A, B, C, D, E);
+impl_plain_components!(A, B, C, D, E, F);
+impl_plain_components!(A, B, C, D, E, F, G);
+impl_plain_components!(A, B, C, D, E, F, G, H);
+impl_plain_components!(A, B, C, D, E, F, G, H, I);
+impl_plain_components!(A, B, C, D, E, F, G, H, I, J);
+impl_plain_components!(A, B, C, D, E, F, G, H, I, J, K);
+impl_plain_components!(A, B, C, D, E, F, G, H, I, J, K, L);
+impl_plain_components!(A, B, C, D, E, F, G, H, I, J, K, L, M);
+impl_plain_components!(A, B, C, D, E, F, G, H, I, J, K, L, M, N);
+impl_plain_components!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
+impl_plain_components!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
+
+const MAGIC: [u8; 8] = *b"LOCALSNP";
+const VERSION: u64 = 2;
+/// Magic, version and entity count.
+const HEADER: usize = 24;
+/// Entity checksum, directory offset and column count.
+const FOOTER: usize = 24;
+/// Id, layout, row count, offset, byte length and checksum of a column.
+const ENTRY: usize = 56;
+/// Alignment of the column blobs within the file, the largest row alignment supported.
+const BLOB_ALIGN: usize = 64;
+
+/// 64 bit FNV-1a over the little endian words of the bytes, the tail padded with zeros.
+fn checksum(bytes: &[u8]) -> u64 {
+    const OFFSET: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    let words = bytes.chunks(8).map(|chunk| {
+        let mut word = [0; 8];
+        word[..chunk.len()].copy_from_slice(chunk);
+        u64::from_le_bytes(word)
+    });
+    words.fold(OFFSET, |hash, word| (hash ^ word).wrapping_mul(PRIME))
+}
+
+/// Hash of the type name and the size and alignment of its rows on this target, so that a
+/// column is only mapped back as the type it was written as.
+fn layout<T>() -> u64 {
+    let target = [
+        size_of::<Row<T>>() as u64,
+        align_of::<Row<T>>() as u64,
+        cfg!(target_endian = "big") as u64,
+    ];
+    let mut bytes = type_name::<T>().as_bytes().to_vec();
+    bytes.extend(target.iter().flat_map(|word| word.to_le_bytes()));
+    checksum(&bytes)
+}
+
+/// The rows as raw bytes, or `None` if they contain padding.
+fn row_bytes<T: Plain>(rows: &[Row<T>]) -> Option<&[u8]> {
+    let unpadded = size_of::<Id>() + 2 * size_of::<Tick>() + size_of::<T>();
+    if size_of::<Row<T>>() != unpadded || align_of::<Row<T>>() > BLOB_ALIGN {
+        return None;
+    }
+    // SAFETY: `T` is plain and the row has no padding between its fields, so every byte
+    // of the rows is initialized.
+    Some(unsafe { slice::from_raw_parts(rows.as_ptr() as *const u8, size_of_val(rows)) })
+}
+
+/// The writer of a snapshot, collecting the directory entries of the columns written to it.
+pub struct Blobs<W> {
+    out: W,
+    written: usize,
+    directory: Vec<u8>,
+    columns: u64,
+}
+
+impl<W: Write> Blobs<W> {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.out.write_all(bytes)?;
+        self.written += bytes.len();
+        Ok(())
+    }
+
+    fn pad(&mut self, align: usize) -> io::Result<()> {
+        let padding = (align - self.written % align) % align;
+        self.write(&[0; BLOB_ALIGN][..padding])
+    }
+
+    fn column<T: Plain>(&mut self, id: Id, rows: &[Row<T>]) -> io::Result<()> {
+        let bytes = row_bytes(rows).ok_or_else(|| {
+            invalid(format!("rows of `{}` contain padding or are overaligned", type_name::<T>()))
+        })?;
+
+        self.pad(BLOB_ALIGN)?;
+        let offset = self.written;
+        self.write(bytes)?;
+
+        self.directory.extend_from_slice(&id);
+        for field in [layout::<T>(), rows.len() as u64, offset as u64, bytes.len() as u64, checksum(bytes)] {
+            self.directory.extend_from_slice(&field.to_le_bytes());
+        }
+        self.columns += 1;
+        Ok(())
+    }
+}
+
+/// Writes the world's entity ids and its columns storing one of the types `C` as a
+/// snapshot, see [`World::snapshot`].
+pub(super) fn write<C: PlainComponents, W: Write>(world: &World, out: W) -> io::Result<()> {
+    let mut blobs = Blobs {
+        out,
+        written: 0,
+        directory: Vec::new(),
+        columns: 0,
+    };
+
+    blobs.write(&MAGIC)?;
+    blobs.write(&VERSION.to_le_bytes())?;
+    blobs.write(&(world.entities.len() as u64).to_le_bytes())?;
+
+    let mut entities = Vec::with_capacity(world.entities.len() * size_of::<Id>());
+    for entity in &world.entities {
+        entities.extend_from_slice(&entity.id);
+    }
+    blobs.write(&entities)?;
+
+    for column in &world.components {
+        C::write_column(column, &mut blobs)?;
+    }
+
+    blobs.pad(8)?;
+    let directory = blobs.written as u64;
+    let entries = std::mem::take(&mut blobs.directory);
+    blobs.write(&entries)?;
+    blobs.write(&checksum(&entities).to_le_bytes())?;
+    blobs.write(&directory.to_le_bytes())?;
+    let columns = blobs.columns;
+    blobs.write(&columns.to_le_bytes())?;
+    blobs.out.flush()
+}
+
+fn invalid(reason: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, reason.into())
+}
+
+fn word(bytes: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap())
+}
+
+struct Entry {
+    id: Id,
+    layout: u64,
+    rows: usize,
+    offset: usize,
+    len: usize,
+}
+
+/// A snapshot written by [`World::snapshot`] and mapped into memory, giving read-only
+/// access to its columns without deserializing them.
+///
+/// The file starts with a header of magic, version and entity count, followed by the
+/// entity ids, the column blobs aligned to 64 bytes, a directory with the id, layout hash,
+/// row count, offset, length and checksum of each blob, and a footer pointing at it.
+pub struct Snapshot {
+    map: Mmap,
+    entities: usize,
+    columns: Vec<Entry>,
+}
+
+impl Snapshot {
+    /// Maps the snapshot at `path` and validates its structure and checksums.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while the snapshot is alive.
+    pub unsafe fn open(path: impl AsRef<Path>) -> io::Result<Snapshot> {
+        let file = File::open(path)?;
+        let map = Mmap::map(&file)?;
+        Snapshot::validate(map)
+    }
+
+    /// Validates a snapshot written into a writable map, e.g. an anonymous one of exactly
+    /// its length, and makes the map read-only.
+    pub fn from_map(map: MmapMut) -> io::Result<Snapshot> {
+        Snapshot::validate(map.make_read_only()?)
+    }
+
+    fn validate(map: Mmap) -> io::Result<Snapshot> {
+        let bytes = &map[..];
+        if bytes.len() < HEADER + FOOTER || bytes[..8] != MAGIC {
+            return Err(invalid("not a snapshot"));
+        }
+        if word(bytes, 8) != VERSION {
+            return Err(invalid("unsupported snapshot version"));
+        }
+
+        let entities = word(bytes, 16) as usize;
+        let footer = bytes.len() - FOOTER;
+        let directory = word(bytes, footer + 8) as usize;
+        let columns = word(bytes, footer + 16) as usize;
+
+        let ids_end = entities
+            .checked_mul(size_of::<Id>())
+            .and_then(|len| len.checked_add(HEADER))
+            .filter(|&end| end <= directory)
+            .ok_or_else(|| invalid("entity ids out of bounds"))?;
+        if checksum(&bytes[HEADER..ids_end]) != word(bytes, footer) {
+            return Err(invalid("entity ids checksum mismatch"));
+        }
+        if columns.checked_mul(ENTRY).and_then(|len| len.checked_add(directory)) != Some(footer) {
+            return Err(invalid("directory out of bounds"));
+        }
+
+        let mut entries = Vec::with_capacity(columns);
+        for raw in bytes[directory..footer].chunks_exact(ENTRY) {
+            let entry = Entry {
+                id: raw[..16].try_into().unwrap(),
+                layout: word(raw, 16),
+                rows: word(raw, 24) as usize,
+                offset: word(raw, 32) as usize,
+                len: word(raw, 40) as usize,
+            };
+            let blob = entry
+                .offset
+                .checked_add(entry.len)
+                .filter(|&end| entry.offset >= ids_end && end <= directory && entry.offset & (BLOB_ALIGN - 1) == 0)
+                .map(|end| &bytes[entry.offset..end])
+                .ok_or_else(|| invalid("column blob out of bounds"))?;
+            if checksum(blob) != word(raw, 48) {
+                return Err(invalid("column blob checksum mismatch"));
+            }
+            entries.push(entry);
+        }
+
+        Ok(Snapshot {
+            map,
+            entities,
+            columns: entries,
+        })
+    }
+
+    pub fn entities(&self) -> &[Id] {
+        let ids = &self.map[HEADER..HEADER + self.entities * size_of::<Id>()];
+        // SAFETY: ids are byte arrays, which have no alignment requirement.
+        unsafe { slice::from_raw_parts(ids.as_ptr() as *const Id, self.entities) }
+    }
+
+    /// The rows of the column `id`, which must have been written as type `T`.
+    pub fn column<T: Plain>(&self, id: Id) -> Result<&[Row<T>], ComponentError> {
+        let entry = self
+            .columns
+            .iter()
+            .find(|entry| entry.id == id)
+            .ok_or(ComponentError::NoColumn { id })?;
+        let fits = entry.rows.checked_mul(size_of::<Row<T>>()) == Some(entry.len);
+        if entry.layout != layout::<T>() || !fits || align_of::<Row<T>>() > BLOB_ALIGN {
+            return Err(ComponentError::LayoutMismatch {
+                id,
+                requested: type_name::<T>(),
+            });
+        }
+
+        let blob = &self.map[entry.offset..entry.offset + entry.len];
+        // SAFETY: the mapping is page aligned and the blob aligned to `BLOB_ALIGN`, which
+        // the row was checked not to exceed. The layout hash matches the row layout of `T`,
+        // which is plain, so any bytes are valid rows.
+        Ok(unsafe { slice::from_raw_parts(blob.as_ptr() as *const Row<T>, entry.rows) })
+    }
+}
+
+/// The rows of a snapshot column, kept mapped by the snapshot they point into, see
+/// [`World::map_snapshot`].
+pub(super) struct MappedRows<T> {
+    snapshot: Arc<Snapshot>,
+    offset: usize,
+    len: usize,
+    component: PhantomData<fn() -> T>,
+}
+
+impl<T> MappedRows<T> {
+    pub(super) fn rows(&self) -> &[Row<T>] {
+        // SAFETY: the rows were checked to be a column of `T` when mapping them, see
+        // `Snapshot::column`.
+        unsafe { slice::from_raw_parts(self.snapshot.map[self.offset..].as_ptr() as *const Row<T>, self.len) }
+    }
+}
+
+/// Mapped rows are read-only, only dropping none of them passes.
+impl<T: Plain> ColumnData for MappedRows<T> {
+    fn as_any(&self) -> &(dyn Any + Sync + Send) {
+        self
+    }
+
+    fn swap_remove_row(&self, _index: usize, _hooks: &(dyn Any + Sync + Send)) -> Option<(Id, usize)> {
+        panic!("rows of `{}` are mapped from a snapshot and can't be removed", type_name::<T>())
+    }
+
+    fn sort_rows(&self, _relocated: &mut dyn FnMut(&Id, usize)) {
+        panic!("rows of `{}` are mapped from a snapshot and can't be sorted", type_name::<T>())
+    }
+
+    fn retain_rows(
+        &self,
+        keep: &dyn Fn(&Id) -> bool,
+        _relocated: &mut dyn FnMut(&Id, usize),
+        _hooks: &(dyn Any + Sync + Send),
+    ) {
+        assert!(
+            self.rows().iter().all(|row| keep(&row.entity)),
+            "rows of `{}` are mapped from a snapshot and can't be removed",
+            type_name::<T>()
+        );
+    }
+}
+
+/// Creates a world of the snapshot's entities and its columns storing one of the types
+/// `C`, see [`World::map_snapshot`].
+pub(super) fn map<C: PlainComponents>(snapshot: Snapshot) -> Result<World, ComponentError> {
+    let snapshot = Arc::new(snapshot);
+    let mut world = World::new();
+    world.entities.reserve(snapshot.entities);
+    world.slots.reserve(snapshot.entities);
+    for id in snapshot.entities() {
+        world.spawn(*id)?;
+    }
+
+    for entry in &snapshot.columns {
+        C::map_column(&mut world, &snapshot, entry.id)?;
+    }
+    Ok(world)
+}
+
+/// Registers the column `id` of type `T` and puts the snapshot's rows in its place,
+/// indexing them in their entities.
+fn map_rows<T: Plain>(world: &mut World, snapshot: &Arc<Snapshot>, id: Id) -> Result<(), ComponentError> {
+    let column = world.new_component::<T>(id)?;
+    let rows = snapshot.column::<T>(id)?;
+    for (index, row) in rows.iter().enumerate() {
+        let slot = world.slot(&row.entity).ok_or(ComponentError::NoEntity { id: row.entity })?;
+        let owner = &mut world.entities[slot];
+        owner.component_id.push(id);
+        owner.component_index.push(index);
+    }
+    if !rows.windows(2).all(|pair| pair[0].entity < pair[1].entity) {
+        world.unsorted.insert(id);
+    }
+
+    let offset = rows.as_ptr() as usize - snapshot.map.as_ptr() as usize;
+    let mapped = MappedRows::<T> {
+        snapshot: snapshot.clone(),
+        offset,
+        len: rows.len(),
+        component: PhantomData,
+    };
+    world.components.replace(Column {
+        data: Arc::new(mapped),
+        clock: Arc::new(atomic::AtomicU32::new(0)),
+        hooks: Arc::new(Hooks::<T>::new()),
+        ..column
+    });
+    Ok(())
+}
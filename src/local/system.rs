@@ -0,0 +1,131 @@
+use rayon::prelude::*;
+
+use smallvec::SmallVec;
+
+use super::{unseen, ComponentError, Id, Query, QueryData, QueryKey, ResourceData, Seen, World, QUERY_INLINE};
+
+/// The columns and resources a system reads and writes, derived from its [`QueryData`]
+/// and [`ResourceData`]. Resources are identified by the [`resource_id`](super::resource_id)
+/// of their type, which never equals the id of a column.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Access {
+    reads: SmallVec<[Id; QUERY_INLINE]>,
+    writes: SmallVec<[Id; QUERY_INLINE]>,
+}
+
+impl Access {
+    pub fn declare(&mut self, id: Id, write: bool) {
+        if write {
+            self.writes.push(id);
+        } else {
+            self.reads.push(id);
+        }
+    }
+
+    pub fn reads(&self) -> &[Id] {
+        &self.reads
+    }
+
+    pub fn writes(&self) -> &[Id] {
+        &self.writes
+    }
+
+    /// Whether one of the two writes a column or resource that the other reads or writes,
+    /// in which case they would contend for its lock if run at the same time.
+    pub fn conflicts(&self, other: &Access) -> bool {
+        self.writes.iter().any(|id| other.reads.contains(id) || other.writes.contains(id))
+            || other.writes.iter().any(|id| self.reads.contains(id))
+    }
+}
+
+/// A unit of work over the matches of one query and a set of resources, see [`Schedule`].
+pub trait System: Send {
+    type Query: QueryData;
+
+    /// The resources locked for the run, `()` for none.
+    type Resources: ResourceData;
+
+    fn run(
+        &mut self,
+        query: Query<'_, <Self::Query as QueryData>::Columns<'_>>,
+        resources: <Self::Resources as ResourceData>::Guards<'_>,
+    );
+}
+
+/// A [`System`] together with the resolved terms and change filter state of its query.
+trait Scheduled: Send {
+    fn run(&mut self, world: &World) -> Result<(), ComponentError>;
+}
+
+struct Resolved<S> {
+    system: S,
+    key: QueryKey,
+    seen: Seen,
+}
+
+impl<S: System> Scheduled for Resolved<S> {
+    fn run(&mut self, world: &World) -> Result<(), ComponentError> {
+        let query = world.cached_query::<S::Query>(&self.key, &self.seen)?;
+        let resources = S::Resources::lock(world)?;
+        self.system.run(query, resources);
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct Batch {
+    systems: Vec<Box<dyn Scheduled>>,
+    access: Vec<Access>,
+}
+
+/// Systems grouped into batches that don't conflict within, see [`Access::conflicts`].
+///
+/// Batches run one after the other, the systems of a batch run in parallel on the rayon
+/// thread pool. Conflicting systems run in the order they were added.
+#[derive(Default)]
+pub struct Schedule {
+    batches: Vec<Batch>,
+}
+
+impl Schedule {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the cached query of `system` and puts it into the batch after the last one
+    /// holding a conflicting system.
+    ///
+    /// Systems whose resources alias are rejected here rather than when they first run.
+    pub fn add_system<S: System + 'static>(&mut self, world: &mut World, system: S) -> Result<(), ComponentError> {
+        S::Resources::check()?;
+        let key = S::Query::key(world)?;
+        world.new_query(&key);
+        let mut access = S::Query::access(&key);
+        S::Resources::access(&mut access);
+
+        let batch = self.batches
+            .iter()
+            .rposition(|batch| batch.access.iter().any(|other| other.conflicts(&access)))
+            .map_or(0, |conflicting| conflicting + 1);
+        if batch == self.batches.len() {
+            self.batches.push(Batch::default());
+        }
+        let batch = &mut self.batches[batch];
+        let seen = unseen(key.len());
+        batch.systems.push(Box::new(Resolved { system, key, seen }));
+        batch.access.push(access);
+        Ok(())
+    }
+
+    /// The access of the systems in each batch.
+    pub fn batches(&self) -> impl Iterator<Item = &[Access]> {
+        self.batches.iter().map(|batch| &batch.access[..])
+    }
+
+    pub fn run(&mut self, world: &World) -> Result<(), ComponentError> {
+        for batch in &mut self.batches {
+            batch.systems.par_iter_mut().try_for_each(|system| system.run(world))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use cgmath::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::EcsBenchmark;
+use super::{Id, World};
+
+/// Levels of the hierarchy, including the roots.
+pub const DEPTH: usize = 10;
+
+/// Transform relative to the parent.
+#[derive(Copy, Clone)]
+pub struct Transform(Matrix4<f32>);
+
+/// Transform relative to the root, composed from the transforms along the way down.
+#[derive(Copy, Clone)]
+pub struct GlobalTransform(Matrix4<f32>);
+
+pub struct Benchmark {
+    world: Box<World>,
+    roots: Vec<Id>,
+}
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = Box::new(World::new());
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut levels: Vec<Vec<Id>> = Vec::with_capacity(DEPTH);
+
+        for level in 0..DEPTH {
+            let tf = world.register::<Transform>().unwrap();
+            let mut tf = tf.write().unwrap();
+            let global = world.register::<GlobalTransform>().unwrap();
+            let mut global = global.write().unwrap();
+
+            let ids = (0..size)
+                .map(|i| {
+                    let mut entity = world.new_entity();
+                    let offset = Vector3::new(i as f32, level as f32, 0.0);
+                    entity.add_component(&mut tf, Transform(Matrix4::from_translation(offset)));
+                    entity.add_component(&mut global, GlobalTransform(Matrix4::identity()));
+                    entity.id
+                })
+                .collect();
+            levels.push(ids);
+        }
+
+        // Every entity below the roots hangs off a random entity of the level above.
+        for level in 1..DEPTH {
+            for i in 0..size {
+                let parent = levels[level - 1][rng.gen_range(0..size)];
+                world.set_parent(&levels[level][i], &parent).unwrap();
+            }
+        }
+
+        Benchmark {
+            world,
+            roots: levels.swap_remove(0),
+        }
+    }
+
+    fn run(&mut self) {
+        let world = &self.world;
+        let tf = world.column::<Transform>().unwrap();
+        let tf = tf.read::<Transform>().unwrap();
+        let global = world.column::<GlobalTransform>().unwrap();
+        let mut global = global.write::<GlobalTransform>().unwrap();
+
+        let mut stack: Vec<(Id, Matrix4<f32>)> = self.roots.iter().map(|root| (*root, Matrix4::identity())).collect();
+        while let Some((id, parent)) = stack.pop() {
+            let entity = &world.entities[world.slot(&id).unwrap()];
+            let composed = parent * tf[entity.index_of(&tf.id).unwrap()].inner.0;
+            global.modify(entity.index_of(&global.id).unwrap()).0 = composed;
+            stack.extend(world.children(&id).iter().map(|child| (*child, composed)));
+        }
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let global = self.world.column::<GlobalTransform>().unwrap();
+        let global = global.read::<GlobalTransform>().unwrap();
+        global.iter().map(|row| row.inner.0.w.x as f64).sum()
+    }
+}
@@ -0,0 +1,94 @@
+use crate::EcsBenchmark;
+use super::World;
+
+pub struct Wide<const N: usize>(f32);
+
+macro_rules! spawn_wide {
+    ($world:ident, $size:expr; $( $n:tt ),*) => {{
+        let columns = ($( $world.register::<Wide<$n>>().unwrap(), )*);
+        let mut guards = ($( columns.$n.write::<Wide<$n>>().unwrap(), )*);
+        for _ in 0..$size {
+            let mut entity = $world.new_entity();
+            $( entity.add_component(&mut guards.$n, Wide::<$n>(1.0)); )*
+        }
+    }};
+}
+
+/// Inserts entities with as many components as an entity stores inline, then queries
+/// them.
+pub struct Benchmark {
+    size: usize,
+    world: Option<Box<World>>,
+    /// Sum over the query run after inserting, see [`iterate`].
+    sum: f64,
+}
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        Benchmark { size, world: None, sum: 0.0 }
+    }
+
+    fn run(&mut self) {
+        let mut world = Box::new(World::new());
+        spawn_wide!(world, self.size; 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15);
+        self.sum = iterate(&mut world);
+        self.world = Some(world);
+    }
+
+    fn checksum(&mut self) -> f64 {
+        self.sum
+    }
+
+    fn teardown(&mut self) {
+        self.world = None;
+    }
+}
+
+/// Inserts entities with one component more than an entity stores inline, so that
+/// every entity's component list spills to the heap, then queries them.
+pub struct SpilledBenchmark {
+    size: usize,
+    world: Option<Box<World>>,
+    /// Sum over the query run after inserting, see [`iterate`].
+    sum: f64,
+}
+
+impl EcsBenchmark for SpilledBenchmark {
+    fn name() -> &'static str {
+        super::SPILLED_NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        SpilledBenchmark { size, world: None, sum: 0.0 }
+    }
+
+    fn run(&mut self) {
+        let mut world = Box::new(World::new());
+        spawn_wide!(world, self.size; 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16);
+        self.sum = iterate(&mut world);
+        self.world = Some(world);
+    }
+
+    fn checksum(&mut self) -> f64 {
+        self.sum
+    }
+
+    fn teardown(&mut self) {
+        self.world = None;
+    }
+}
+
+/// Runs a query over the first and the last inline component of the wide entities, whose
+/// matching looks both up in each entity's component list, and sums the first.
+fn iterate(world: &mut World) -> f64 {
+    let mut query = world.query::<(&Wide<0>, &Wide<15>)>().unwrap();
+    let mut sum = 0.0;
+    while let Some((first, _)) = query.next_match() {
+        sum += first.0 as f64;
+    }
+    sum
+}
@@ -1,16 +1,23 @@
 use planck_ecs::*;
+
+use crate::EcsBenchmark;
+
 struct A(f32);
 struct B(f32);
 
 pub struct Benchmark(Vec<Entity>, Components<A>, Components<B>);
 
-impl Benchmark {
-    pub fn new() -> Self {
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
         let mut entities = Entities::default();
         let mut comp1 = Components::<A>::default();
         let comp2 = Components::<B>::default();
 
-        let entities = (0..10000)
+        let entities = (0..size)
             .map(|_| {
                 let e = entities.create();
                 comp1.insert(e, A(0.0));
@@ -20,7 +27,7 @@ impl Benchmark {
         Self(entities, comp1, comp2)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         let b_storage = &mut self.2;
         for entity in &self.0 {
             b_storage.insert(*entity, B(0.0));
@@ -30,4 +37,17 @@ impl Benchmark {
             b_storage.remove(*entity);
         }
     }
+
+    fn checksum(&mut self) -> f64 {
+        let a_storage = &self.1;
+        let b_storage = &self.2;
+        let mut count = 0;
+        for _ in join!(&a_storage) {
+            count += 1;
+        }
+        for _ in join!(&b_storage) {
+            count += 1;
+        }
+        count as f64
+    }
 }
@@ -1,11 +1,13 @@
 use planck_ecs::*;
 
+use crate::EcsBenchmark;
+
 macro_rules! create_entities {
-    ($world:ident; $( $variants:ident ),*) => {
+    ($world:ident, $size:expr; $( $variants:ident ),*) => {
         $(
             struct $variants(f32);
             $world.initialize::<Components<$variants>>();
-            (0..20)
+            (0..$size)
             .for_each(|_| {
                 let e = $world.get_mut::<Entities>().unwrap().create();
                 $world.get_mut::<Components<_>>().unwrap().insert(e, $variants(0.0));
@@ -25,17 +27,31 @@ fn frag_iter_system(data_storage: &mut Components<Data>) -> SystemResult {
 
 pub struct Benchmark(World, System);
 
-impl Benchmark {
-    pub fn new() -> Self {
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
         let mut world = World::default();
         world.initialize::<Entities>();
         world.initialize::<Components<Data>>();
-        create_entities!(world; A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z);
+        create_entities!(world, size; A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z);
 
         Self(world, frag_iter_system.system())
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         self.1.run(&self.0).unwrap();
     }
+
+    fn checksum(&mut self) -> f64 {
+        let data_storage = self.0.get::<Components<Data>>().unwrap();
+        let data_storage = &*data_storage;
+        let mut sum = 0.0;
+        for data in join!(&data_storage) {
+            sum += data.0 as f64;
+        }
+        sum
+    }
 }
@@ -2,6 +2,9 @@ pub mod add_remove;
 pub mod frag_iter;
 // We don't have inner parallelism, only outer.
 //pub mod heavy_compute;
+pub mod random_access;
 pub mod schedule;
 pub mod simple_insert;
 pub mod simple_iter;
+
+pub const NAME: &str = "planck_ecs";
@@ -0,0 +1,49 @@
+use cgmath::*;
+use planck_ecs::*;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::EcsBenchmark;
+
+#[derive(Copy, Clone)]
+struct Position(Vector3<f32>);
+#[derive(Copy, Clone)]
+struct Velocity(Vector3<f32>);
+
+pub struct Benchmark(Vec<Entity>, Components<Position>, Components<Velocity>, f64);
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut entities = Entities::default();
+        let mut position_storage = Components::<Position>::default();
+        let mut velocity_storage = Components::<Velocity>::default();
+        let mut shuffled = (0..size)
+            .map(|i| {
+                let e = entities.create();
+                position_storage.insert(e, Position(Vector3::new(i as f32, 0.0, 0.0)));
+                velocity_storage.insert(e, Velocity(Vector3::unit_x()));
+                e
+            })
+            .collect::<Vec<_>>();
+        shuffled.shuffle(&mut StdRng::seed_from_u64(0));
+
+        Self(shuffled, position_storage, velocity_storage, 0.0)
+    }
+
+    fn run(&mut self) {
+        let mut sum = 0.0;
+        for &entity in &self.0 {
+            let position = self.1.get(entity).unwrap();
+            let velocity = self.2.get(entity).unwrap();
+            sum += (position.0.x + velocity.0.x) as f64;
+        }
+        self.3 = sum;
+    }
+
+    fn checksum(&mut self) -> f64 {
+        self.3
+    }
+}
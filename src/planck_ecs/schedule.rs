@@ -1,4 +1,7 @@
 use planck_ecs::*;
+
+use crate::EcsBenchmark;
+
 struct A(f32);
 struct B(f32);
 struct C(f32);
@@ -28,8 +31,12 @@ fn ce_system(c_store: &mut Components<C>, e_store: &mut Components<E>) -> System
 
 pub struct Benchmark(World, Dispatcher);
 
-impl Benchmark {
-    pub fn new() -> Self {
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
         let mut world = World::default();
         world.initialize::<Entities>();
         world.initialize::<Components<A>>();
@@ -37,34 +44,34 @@ impl Benchmark {
         world.initialize::<Components<C>>();
         world.initialize::<Components<D>>();
         world.initialize::<Components<E>>();
-        (0..10000).for_each(|_| {
+        (0..size).for_each(|_| {
             let e = world.get_mut::<Entities>().unwrap().create();
-            world.get_mut::<Components<_>>().unwrap().insert(e, A(0.0));
+            world.get_mut::<Components<_>>().unwrap().insert(e, A(1.0));
         });
-        (0..10000).for_each(|_| {
+        (0..size).for_each(|_| {
             let e = world.get_mut::<Entities>().unwrap().create();
-            world.get_mut::<Components<_>>().unwrap().insert(e, A(0.0));
-            world.get_mut::<Components<_>>().unwrap().insert(e, B(0.0));
+            world.get_mut::<Components<_>>().unwrap().insert(e, A(1.0));
+            world.get_mut::<Components<_>>().unwrap().insert(e, B(2.0));
         });
-        (0..10000).for_each(|_| {
+        (0..size).for_each(|_| {
             let e = world.get_mut::<Entities>().unwrap().create();
-            world.get_mut::<Components<_>>().unwrap().insert(e, A(0.0));
-            world.get_mut::<Components<_>>().unwrap().insert(e, B(0.0));
-            world.get_mut::<Components<_>>().unwrap().insert(e, C(0.0));
+            world.get_mut::<Components<_>>().unwrap().insert(e, A(1.0));
+            world.get_mut::<Components<_>>().unwrap().insert(e, B(2.0));
+            world.get_mut::<Components<_>>().unwrap().insert(e, C(3.0));
         });
-        (0..10000).for_each(|_| {
+        (0..size).for_each(|_| {
             let e = world.get_mut::<Entities>().unwrap().create();
-            world.get_mut::<Components<_>>().unwrap().insert(e, A(0.0));
-            world.get_mut::<Components<_>>().unwrap().insert(e, B(0.0));
-            world.get_mut::<Components<_>>().unwrap().insert(e, C(0.0));
-            world.get_mut::<Components<_>>().unwrap().insert(e, D(0.0));
+            world.get_mut::<Components<_>>().unwrap().insert(e, A(1.0));
+            world.get_mut::<Components<_>>().unwrap().insert(e, B(2.0));
+            world.get_mut::<Components<_>>().unwrap().insert(e, C(3.0));
+            world.get_mut::<Components<_>>().unwrap().insert(e, D(4.0));
         });
-        (0..10000).for_each(|_| {
+        (0..size).for_each(|_| {
             let e = world.get_mut::<Entities>().unwrap().create();
-            world.get_mut::<Components<_>>().unwrap().insert(e, A(0.0));
-            world.get_mut::<Components<_>>().unwrap().insert(e, B(0.0));
-            world.get_mut::<Components<_>>().unwrap().insert(e, C(0.0));
-            world.get_mut::<Components<_>>().unwrap().insert(e, E(0.0));
+            world.get_mut::<Components<_>>().unwrap().insert(e, A(1.0));
+            world.get_mut::<Components<_>>().unwrap().insert(e, B(2.0));
+            world.get_mut::<Components<_>>().unwrap().insert(e, C(3.0));
+            world.get_mut::<Components<_>>().unwrap().insert(e, E(5.0));
         });
 
         let dispatcher = DispatcherBuilder::new()
@@ -76,7 +83,25 @@ impl Benchmark {
         Self(world, dispatcher)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         self.1.run_par(&self.0).unwrap();
     }
+
+    fn checksum(&mut self) -> f64 {
+        fn sum<T: 'static>(world: &World, value: fn(&T) -> f32) -> f32 {
+            let storage = world.get::<Components<T>>().unwrap();
+            let storage = &*storage;
+            let mut sum = 0.0;
+            for component in join!(&storage) {
+                sum += value(component);
+            }
+            sum
+        }
+
+        let b = sum::<B>(&self.0, |b| b.0);
+        let c = sum::<C>(&self.0, |c| c.0);
+        let d = sum::<D>(&self.0, |d| d.0);
+        let e = sum::<E>(&self.0, |e| e.0);
+        (b + c + d + e) as f64
+    }
 }
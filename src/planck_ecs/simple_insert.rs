@@ -1,6 +1,8 @@
 use cgmath::*;
 use planck_ecs::*;
 
+use crate::EcsBenchmark;
+
 #[derive(Copy, Clone)]
 struct Transform(Matrix4<f32>);
 #[derive(Copy, Clone)]
@@ -10,21 +12,25 @@ struct Rotation(Vector3<f32>);
 #[derive(Copy, Clone)]
 struct Velocity(Vector3<f32>);
 
-pub struct Benchmark;
+pub struct Benchmark(usize, Option<Components<Position>>);
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
 
-impl Benchmark {
-    pub fn new() -> Self {
-        Self
+    fn setup(size: usize) -> Self {
+        Self(size, None)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         let mut entities = Entities::default();
         let mut comp1 = Components::<Transform>::default();
         let mut comp2 = Components::<Position>::default();
         let mut comp3 = Components::<Rotation>::default();
         let mut comp4 = Components::<Velocity>::default();
 
-        let en = (0..1_000_000).map(|_| entities.create()).collect::<Vec<_>>();
+        let en = (0..self.0).map(|_| entities.create()).collect::<Vec<_>>();
         en.iter().for_each(|e| {comp1.insert(*e, Transform(Matrix4::<f32>::from_scale(1.0)));});
         en.iter().for_each(|e| {comp2.insert(*e, Position(Vector3::unit_x()));});
         en.iter().for_each(|e| {comp3.insert(*e, Rotation(Vector3::unit_x()));});
@@ -37,5 +43,20 @@ impl Benchmark {
             comp3.insert(e, Rotation(Vector3::unit_x()));
             comp4.insert(e, Velocity(Vector3::unit_x()));
         });*/
+
+        self.1 = Some(comp2);
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let position_storage = self.1.as_ref().unwrap();
+        let mut sum = 0.0;
+        for position in join!(&position_storage) {
+            sum += position.0.x as f64;
+        }
+        sum
+    }
+
+    fn teardown(&mut self) {
+        self.1 = None;
     }
 }
@@ -1,6 +1,8 @@
 use cgmath::*;
 use planck_ecs::*;
 
+use crate::EcsBenchmark;
+
 #[derive(Copy, Clone)]
 struct Position(Vector3<f32>);
 #[derive(Copy, Clone)]
@@ -8,12 +10,16 @@ struct Velocity(Vector3<f32>);
 
 pub struct Benchmark(Components<Velocity>, Components<Position>);
 
-impl Benchmark {
-    pub fn new() -> Self {
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
         let mut entities = Entities::default();
         let mut position_storage = Components::<Position>::default();
         let mut velocity_storage = Components::<Velocity>::default();
-        (0..1_000_000).for_each(|_| {
+        (0..size).for_each(|_| {
             let e = entities.create();
             position_storage.insert(e, Position(Vector3::unit_x()));
             velocity_storage.insert(e, Velocity(Vector3::unit_x()));
@@ -22,11 +28,20 @@ impl Benchmark {
         Self(velocity_storage, position_storage)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         let velocity_storage = &mut self.0;
         let position_storage = &mut self.1;
         for (velocity, mut position) in join!(&mut velocity_storage && &mut position_storage) {
             position.as_mut().unwrap().0 += velocity.unwrap().0;
         }
     }
+
+    fn checksum(&mut self) -> f64 {
+        let position_storage = &self.1;
+        let mut sum = 0.0;
+        for position in join!(&position_storage) {
+            sum += position.0.x as f64;
+        }
+        sum
+    }
 }
@@ -0,0 +1,56 @@
+use std::collections::HashMap;
+
+use crate::EcsBenchmark;
+
+struct A(f32);
+struct B(f32);
+
+pub struct Benchmark {
+    next: u64,
+    entities: Vec<u64>,
+    a: HashMap<u64, A>,
+    b: HashMap<u64, B>,
+}
+
+impl Benchmark {
+    fn spawn(&mut self, size: usize) {
+        for _ in 0..size {
+            let entity = self.next;
+            self.next += 1;
+            self.a.insert(entity, A(0.0));
+            self.b.insert(entity, B(0.0));
+            self.entities.push(entity);
+        }
+    }
+}
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut bench = Self {
+            next: 0,
+            entities: Vec::with_capacity(size),
+            a: HashMap::new(),
+            b: HashMap::new(),
+        };
+        bench.spawn(size);
+        bench
+    }
+
+    fn run(&mut self) {
+        let size = self.entities.len();
+        for entity in self.entities.drain(..) {
+            self.a.remove(&entity);
+            self.b.remove(&entity);
+        }
+
+        self.spawn(size);
+    }
+
+    fn checksum(&mut self) -> f64 {
+        (self.a.len() + self.b.len()) as f64
+    }
+}
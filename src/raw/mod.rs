@@ -1,2 +1,5 @@
+pub mod despawn;
 pub mod simple_insert;
 pub mod simple_iter;
+
+pub const NAME: &str = "raw";
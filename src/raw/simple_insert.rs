@@ -2,6 +2,8 @@ use std::collections::HashMap;
 
 use cgmath::*;
 
+use crate::EcsBenchmark;
+
 #[derive(Copy, Clone)]
 struct Transform(Matrix4<f32>);
 #[derive(Copy, Clone)]
@@ -13,25 +15,48 @@ struct Rotation(Vector3<f32>);
 #[derive(Copy, Clone)]
 struct Velocity(Vector3<f32>);
 
-pub struct Benchmark;
+/// One map per component type, keyed by entity.
+type Maps = (
+    HashMap<u64, Transform>,
+    HashMap<u64, Position>,
+    HashMap<u64, Rotation>,
+    HashMap<u64, Velocity>,
+);
+
+pub struct Benchmark(usize, Option<Maps>);
 
-impl Benchmark {
-    pub fn new() -> Self {
-        Self
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
     }
 
-    pub fn run(&mut self) {
+    fn setup(size: usize) -> Self {
+        Self(size, None)
+    }
+
+    fn run(&mut self) {
         let mut tf = HashMap::new();
         let mut pos = HashMap::new();
         let mut rot = HashMap::new();
         let mut vel = HashMap::new();
         
-        (0..1_000_000).for_each(|i: u64| {
+        (0..self.0 as u64).for_each(|i: u64| {
             let entity = i;
             tf.insert(entity, Transform(Matrix4::<f32>::from_scale(1.0)));
             pos.insert(entity, Position(Vector3::unit_x()));
             rot.insert(entity, Rotation(Vector3::unit_x()));
             vel.insert(entity, Velocity(Vector3::unit_x()));
         });
+
+        self.1 = Some((tf, pos, rot, vel));
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let (_, pos, _, _) = self.1.as_ref().unwrap();
+        pos.values().map(|position| position.0.x as f64).sum()
+    }
+
+    fn teardown(&mut self) {
+        self.1 = None;
     }
 }
@@ -2,6 +2,8 @@ use std::collections::HashMap;
 
 use cgmath::*;
 
+use crate::EcsBenchmark;
+
 #[derive(Copy, Clone)]
 struct Transform(Matrix4<f32>);
 #[derive(Copy, Clone)]
@@ -20,14 +22,18 @@ pub struct Benchmark{
     vel: HashMap<u64, Velocity>
 }
 
-impl Benchmark {
-    pub fn new() -> Self {
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
         let mut tf = HashMap::new();
         let mut pos = HashMap::new();
         let mut rot = HashMap::new();
         let mut vel = HashMap::new();
         
-        (0..1_000_000).for_each(|i: u64| {
+        (0..size as u64).for_each(|i: u64| {
             let entity = i;
             tf.insert(entity, Transform(Matrix4::<f32>::from_angle_x(Rad(1.2))));
             pos.insert(entity, Position(Vector3::unit_x()));
@@ -38,11 +44,15 @@ impl Benchmark {
         Self{tf, pos, rot, vel}
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         for (entity, position) in self.pos.iter_mut() {
             if let Some(velocity) = self.vel.get(entity) {
                 position.0 += velocity.0;
             }
         }
     }
+
+    fn checksum(&mut self) -> f64 {
+        self.pos.values().map(|position| position.0.x as f64).sum()
+    }
 }
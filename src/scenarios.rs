@@ -0,0 +1,195 @@
+use crate::harness::Scenario;
+use crate::*;
+
+const ENTITY_SWEEP: &[usize] = &[100, 1_000, 10_000, 100_000, 1_000_000];
+
+/// Every scenario that is run by the benchmark harness.
+pub fn all() -> Vec<Scenario> {
+    vec![
+        simple_insert(),
+        simple_iter(),
+        frag_iter(),
+        filtered_iter(),
+        add_remove(),
+        despawn(),
+        deferred(),
+        despawn_recursive(),
+        wide_insert(),
+        schedule(),
+        events(),
+        heavy_compute(),
+        change_detection(),
+        random_access(),
+        transform_propagation(),
+        serialize_text(),
+        serialize_binary(),
+    ]
+}
+
+pub fn simple_insert() -> Scenario {
+    Scenario::new("simple_insert", |size| 4 * size as u64, ENTITY_SWEEP)
+        .register::<raw::simple_insert::Benchmark>()
+        .register::<local::simple_insert::Benchmark>()
+        .register::<local::simple_insert::HooksBenchmark>()
+        .register::<local::simple_insert::SpawnBatchBenchmark>()
+        .register::<local::simple_insert::SoaBenchmark>()
+        .register::<local::simple_insert::SequentialBenchmark>()
+        .register::<local::simple_insert::RangesBenchmark>()
+        .register::<tribles::simple_insert::Benchmark>()
+        .register::<specs::simple_insert::Benchmark>()
+        .register::<legion::simple_insert::Benchmark>()
+        .register::<bevy::simple_insert::Benchmark>()
+        .register::<hecs::simple_insert::Benchmark>()
+        //.register::<planck_ecs::simple_insert::Benchmark>()
+        .register::<shipyard::simple_insert::Benchmark>()
+}
+
+pub fn simple_iter() -> Scenario {
+    Scenario::new("simple_iter", |size| size as u64, ENTITY_SWEEP)
+        .register::<raw::simple_iter::Benchmark>()
+        .register::<local::simple_iter::Benchmark>()
+        .register::<local::simple_iter::UncheckedBenchmark>()
+        .register::<local::simple_iter::JoinBenchmark>()
+        .register::<local::simple_iter::SequentialJoinBenchmark>()
+        .register::<local::simple_iter::SystemBenchmark>()
+        .register::<local::archetype::simple_iter::Benchmark>()
+        .register::<tribles::simple_iter::Benchmark>()
+        .register::<specs::simple_iter::Benchmark>()
+        .register::<legion::simple_iter::Benchmark>()
+        .register::<legion_packed::simple_iter::Benchmark>()
+        .register::<bevy::simple_iter::Benchmark>()
+        .register::<hecs::simple_iter::Benchmark>()
+        //.register::<planck_ecs::simple_iter::Benchmark>()
+        .register::<shipyard::simple_iter::Benchmark>()
+}
+
+pub fn frag_iter() -> Scenario {
+    Scenario::new("fragmented_iter", |size| 26 * size as u64, &[2, 20, 200, 2000])
+        .register::<local::frag_iter::Benchmark>()
+        .register::<local::frag_iter::JoinBenchmark>()
+        .register::<local::archetype::frag_iter::Benchmark>()
+        .register::<specs::frag_iter::Benchmark>()
+        .register::<legion::frag_iter::Benchmark>()
+        .register::<bevy::frag_iter::Benchmark>()
+        .register::<hecs::frag_iter::Benchmark>()
+        .register::<planck_ecs::frag_iter::Benchmark>()
+        .register::<shipyard::frag_iter::Benchmark>()
+}
+
+pub fn filtered_iter() -> Scenario {
+    Scenario::new("filtered_iter", |size| size as u64, ENTITY_SWEEP)
+        .register::<local::filtered_iter::Benchmark>()
+        .register::<local::filtered_iter::JoinBenchmark>()
+        .register::<local::filtered_iter::SequentialJoinBenchmark>()
+        .register::<specs::filtered_iter::Benchmark>()
+        .register::<legion::filtered_iter::Benchmark>()
+        .register::<bevy::filtered_iter::Benchmark>()
+}
+
+pub fn schedule() -> Scenario {
+    Scenario::new("schedule", |size| 4 * size as u64, &[100, 1_000, 10_000])
+        .register::<local::schedule::Benchmark>()
+        .register::<legion::schedule::Benchmark>()
+        .register::<legion_packed::schedule::Benchmark>()
+        .register::<bevy::schedule::Benchmark>()
+        .register::<planck_ecs::schedule::Benchmark>()
+        .register::<shipyard::schedule::Benchmark>()
+        .register::<specs::schedule::Benchmark>()
+}
+
+pub fn events() -> Scenario {
+    Scenario::new("events", |size| size as u64, &[1_000, 10_000, 100_000, 1_000_000])
+        .register::<local::events::Benchmark>()
+        .register::<specs::events::Benchmark>()
+}
+
+pub fn heavy_compute() -> Scenario {
+    Scenario::new("heavy_compute", |size| 100 * size as u64, &[100, 1_000, 10_000])
+        .register::<local::heavy_compute::Benchmark>()
+        .register::<legion::heavy_compute::Benchmark>()
+        .register::<legion_packed::heavy_compute::Benchmark>()
+        .register::<bevy::heavy_compute::Benchmark>()
+        .register::<hecs::heavy_compute::Benchmark>()
+        .register::<shipyard::heavy_compute::Benchmark>()
+        .register::<specs::heavy_compute::Benchmark>()
+}
+
+pub fn add_remove() -> Scenario {
+    Scenario::new("add_remove_component", |size| size as u64, &[100, 1_000, 10_000])
+        .register::<local::add_remove::Benchmark>()
+        .register::<local::archetype::add_remove::Benchmark>()
+        .register::<legion::add_remove::Benchmark>()
+        .register::<hecs::add_remove::Benchmark>()
+        .register::<planck_ecs::add_remove::Benchmark>()
+        .register::<shipyard::add_remove::Benchmark>()
+        .register::<specs::add_remove::Benchmark>()
+        .register::<bevy::add_remove::Benchmark>()
+}
+
+pub fn despawn() -> Scenario {
+    Scenario::new("despawn", |size| size as u64, &[100, 1_000, 10_000])
+        .register::<raw::despawn::Benchmark>()
+        .register::<local::despawn::Benchmark>()
+        .register::<specs::despawn::Benchmark>()
+        .register::<legion::despawn::Benchmark>()
+        .register::<bevy::despawn::Benchmark>()
+        .register::<hecs::despawn::Benchmark>()
+}
+
+pub fn deferred() -> Scenario {
+    Scenario::new("deferred", |size| size as u64, &[100, 1_000, 10_000])
+        .register::<local::deferred::ImmediateBenchmark>()
+        .register::<local::deferred::Benchmark>()
+}
+
+pub fn despawn_recursive() -> Scenario {
+    Scenario::new("despawn_recursive", |size| size as u64, &[100, 1_000, 10_000])
+        .register::<local::despawn::Benchmark>()
+        .register::<local::despawn::RecursiveBenchmark>()
+}
+
+pub fn wide_insert() -> Scenario {
+    Scenario::new("wide_insert", |size| size as u64, &[100, 1_000, 10_000])
+        .register::<local::wide_insert::Benchmark>()
+        .register::<local::wide_insert::SpilledBenchmark>()
+}
+
+pub fn change_detection() -> Scenario {
+    Scenario::new("change_detection", |size| size as u64, ENTITY_SWEEP)
+        .register::<local::change_detection::Benchmark>()
+        .register::<local::change_detection::ChangedBenchmark>()
+        .register::<local::change_detection::UncheckedBenchmark>()
+}
+
+pub fn random_access() -> Scenario {
+    Scenario::new("random_access", |size| 2 * size as u64, ENTITY_SWEEP)
+        .register::<local::random_access::Benchmark>()
+        .register::<specs::random_access::Benchmark>()
+        .register::<legion::random_access::Benchmark>()
+        .register::<bevy::random_access::Benchmark>()
+        .register::<hecs::random_access::Benchmark>()
+        .register::<planck_ecs::random_access::Benchmark>()
+        .register::<shipyard::random_access::Benchmark>()
+}
+
+pub fn transform_propagation() -> Scenario {
+    Scenario::new("transform_propagation", |size| (local::transform_propagation::DEPTH * size) as u64, &[100, 1_000, 10_000])
+        .register::<local::transform_propagation::Benchmark>()
+        .register::<bevy::transform_propagation::Benchmark>()
+}
+
+pub fn serialize_text() -> Scenario {
+    Scenario::new("serialize_text", |size| 4 * size as u64, &[100, 1_000, 10_000])
+        .register::<local::serialize_text::Benchmark>()
+        .register::<legion::serialize_text::Benchmark>()
+        .register::<hecs::serialize_text::Benchmark>()
+}
+
+pub fn serialize_binary() -> Scenario {
+    Scenario::new("serialize_binary", |size| 4 * size as u64, &[100, 1_000, 10_000])
+        .register::<local::serialize_binary::Benchmark>()
+        .register::<local::serialize_binary::SnapshotBenchmark>()
+        .register::<local::serialize_binary::FileSnapshotBenchmark>()
+        .register::<legion::serialize_binary::Benchmark>()
+        .register::<hecs::serialize_binary::Benchmark>()
+}
@@ -1,17 +1,23 @@
 use shipyard::*;
 
+use crate::EcsBenchmark;
+
 struct A(f32);
 struct B(f32);
 
 pub struct Benchmark(World, Vec<EntityId>);
 
-impl Benchmark {
-    pub fn new() -> Self {
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
         let world = World::default();
 
         let entities = world.run(|mut entities: EntitiesViewMut, mut a: ViewMut<A>| {
             let mut entity_ids = Vec::new();
-            for _ in 0..10_000 {
+            for _ in 0..size {
                 let entity = entities.add_entity(&mut a, A(0.0));
                 entity_ids.push(entity);
             }
@@ -21,7 +27,7 @@ impl Benchmark {
         Self(world, entities)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         self.0.run(|entities: EntitiesViewMut, mut b: ViewMut<B>| {
             for entity in &self.1 {
                 entities.add_component(*entity, &mut b, B(0.0));
@@ -34,4 +40,10 @@ impl Benchmark {
             }
         }).unwrap();
     }
+
+    fn checksum(&mut self) -> f64 {
+        self.0.run(|a: View<A>, b: View<B>| {
+            ((&a).iter().count() + (&b).iter().count()) as f64
+        }).unwrap()
+    }
 }
@@ -2,6 +2,8 @@ use cgmath::*;
 use rayon::prelude::*;
 use shipyard::*;
 
+use crate::EcsBenchmark;
+
 #[derive(Copy, Clone)]
 struct Position(Vector3<f32>);
 
@@ -13,8 +15,12 @@ struct Velocity(Vector3<f32>);
 
 pub struct Benchmark(World);
 
-impl Benchmark {
-    pub fn new() -> Self {
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
         let world = World::default();
 
         world.run(
@@ -23,7 +29,7 @@ impl Benchmark {
              mut positions: ViewMut<Position>,
              mut rotations: ViewMut<Rotation>,
              mut velocities: ViewMut<Velocity>| {
-                for _ in 0..1000 {
+                for _ in 0..size {
                     entities.add_entity(
                         (
                             &mut transforms,
@@ -45,7 +51,7 @@ impl Benchmark {
         Self(world)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         self.0.run(
             |mut positions: ViewMut<Position>, mut transforms: ViewMut<Matrix4<f32>>| {
                 (&mut positions, &mut transforms)
@@ -59,4 +65,13 @@ impl Benchmark {
             },
         ).unwrap();
     }
+
+    fn checksum(&mut self) -> f64 {
+        self.0.run(|positions: View<Position>| {
+            (&positions)
+                .iter()
+                .map(|position| (position.0.x + position.0.y + position.0.z) as f64)
+                .sum()
+        }).unwrap()
+    }
 }
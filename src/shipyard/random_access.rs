@@ -0,0 +1,61 @@
+use cgmath::*;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use shipyard::*;
+
+use crate::EcsBenchmark;
+
+#[derive(Copy, Clone)]
+struct Position(Vector3<f32>);
+
+#[derive(Copy, Clone)]
+struct Velocity(Vector3<f32>);
+
+pub struct Benchmark(World, Vec<EntityId>, f64);
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let world = World::default();
+
+        let mut entities = world.run(
+            |mut entities: EntitiesViewMut,
+             mut positions: ViewMut<Position>,
+             mut velocities: ViewMut<Velocity>| {
+                (0..size)
+                    .map(|i| {
+                        entities.add_entity(
+                            (&mut positions, &mut velocities),
+                            (
+                                Position(Vector3::new(i as f32, 0.0, 0.0)),
+                                Velocity(Vector3::unit_x()),
+                            ),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            },
+        ).unwrap();
+        entities.shuffle(&mut StdRng::seed_from_u64(0));
+
+        Self(world, entities, 0.0)
+    }
+
+    fn run(&mut self) {
+        let entities = &self.1;
+        self.2 = self.0.run(|positions: View<Position>, velocities: View<Velocity>| {
+            let mut sum = 0.0;
+            for &entity in entities {
+                let position = (&positions).get(entity).unwrap();
+                let velocity = (&velocities).get(entity).unwrap();
+                sum += (position.0.x + velocity.0.x) as f64;
+            }
+            sum
+        }).unwrap();
+    }
+
+    fn checksum(&mut self) -> f64 {
+        self.2
+    }
+}
@@ -1,5 +1,7 @@
 use shipyard::*;
 
+use crate::EcsBenchmark;
+
 struct A(f32);
 struct B(f32);
 struct C(f32);
@@ -26,14 +28,18 @@ fn ce(mut c: ViewMut<C>, mut e: ViewMut<E>) {
 
 pub struct Benchmark(World);
 
-impl Benchmark {
-    pub fn new() -> Self {
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
         let world = World::default();
 
         world.run(
             |mut entities: EntitiesViewMut, mut a: ViewMut<A>, mut b: ViewMut<B>| {
-                for _ in 0..10_000 {
-                    entities.add_entity((&mut a, &mut b), (A(0.0), B(0.0)));
+                for _ in 0..size {
+                    entities.add_entity((&mut a, &mut b), (A(1.0), B(2.0)));
                 }
             },
         ).unwrap();
@@ -43,8 +49,8 @@ impl Benchmark {
              mut a: ViewMut<A>,
              mut b: ViewMut<B>,
              mut c: ViewMut<C>| {
-                for _ in 0..10_000 {
-                    entities.add_entity((&mut a, &mut b, &mut c), (A(0.0), B(0.0), C(0.0)));
+                for _ in 0..size {
+                    entities.add_entity((&mut a, &mut b, &mut c), (A(1.0), B(2.0), C(3.0)));
                 }
             },
         ).unwrap();
@@ -55,10 +61,10 @@ impl Benchmark {
              mut b: ViewMut<B>,
              mut c: ViewMut<C>,
              mut d: ViewMut<D>| {
-                for _ in 0..10_000 {
+                for _ in 0..size {
                     entities.add_entity(
                         (&mut a, &mut b, &mut c, &mut d),
-                        (A(0.0), B(0.0), C(0.0), D(0.0)),
+                        (A(1.0), B(2.0), C(3.0), D(4.0)),
                     );
                 }
             },
@@ -70,10 +76,10 @@ impl Benchmark {
              mut b: ViewMut<B>,
              mut c: ViewMut<C>,
              mut e: ViewMut<E>| {
-                for _ in 0..10_000 {
+                for _ in 0..size {
                     entities.add_entity(
                         (&mut a, &mut b, &mut c, &mut e),
-                        (A(0.0), B(0.0), C(0.0), E(0.0)),
+                        (A(1.0), B(2.0), C(3.0), E(5.0)),
                     );
                 }
             },
@@ -89,7 +95,17 @@ impl Benchmark {
         Self(world)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         self.0.run_workload("run").unwrap();
     }
+
+    fn checksum(&mut self) -> f64 {
+        self.0.run(|b: View<B>, c: View<C>, d: View<D>, e: View<E>| {
+            let b: f32 = (&b).iter().map(|b| b.0).sum();
+            let c: f32 = (&c).iter().map(|c| c.0).sum();
+            let d: f32 = (&d).iter().map(|d| d.0).sum();
+            let e: f32 = (&e).iter().map(|e| e.0).sum();
+            (b + c + d + e) as f64
+        }).unwrap()
+    }
 }
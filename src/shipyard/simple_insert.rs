@@ -1,6 +1,8 @@
 use cgmath::*;
 use shipyard::*;
 
+use crate::EcsBenchmark;
+
 #[derive(Copy, Clone)]
 struct Transform(Matrix4<f32>);
 
@@ -13,14 +15,18 @@ struct Rotation(Vector3<f32>);
 #[derive(Copy, Clone)]
 struct Velocity(Vector3<f32>);
 
-pub struct Benchmark;
+pub struct Benchmark(usize, Option<World>);
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
 
-impl Benchmark {
-    pub fn new() -> Self {
-        Self
+    fn setup(size: usize) -> Self {
+        Self(size, None)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         let world = World::default();
 
         world.run(
@@ -29,7 +35,7 @@ impl Benchmark {
              mut positions: ViewMut<Position>,
              mut rotations: ViewMut<Rotation>,
              mut velocities: ViewMut<Velocity>| {
-                for _ in 0..1_000_000 {
+                for _ in 0..self.0 {
                     entities.add_entity(
                         (
                             &mut transforms,
@@ -47,5 +53,21 @@ impl Benchmark {
                 }
             },
         ).unwrap();
+
+        self.1 = Some(world);
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let world = self.1.as_ref().unwrap();
+        world.run(|positions: View<Position>| {
+            (&positions)
+                .iter()
+                .map(|position| position.0.x as f64)
+                .sum()
+        }).unwrap()
+    }
+
+    fn teardown(&mut self) {
+        self.1 = None;
     }
 }
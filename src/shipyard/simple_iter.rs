@@ -1,6 +1,8 @@
 use cgmath::*;
 use shipyard::*;
 
+use crate::EcsBenchmark;
+
 #[derive(Copy, Clone)]
 struct Transform(Matrix4<f32>);
 
@@ -15,8 +17,12 @@ struct Velocity(Vector3<f32>);
 
 pub struct Benchmark(World);
 
-impl Benchmark {
-    pub fn new() -> Self {
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
         let world = World::default();
 
         world.run(
@@ -25,7 +31,7 @@ impl Benchmark {
              mut positions: ViewMut<Position>,
              mut rotations: ViewMut<Rotation>,
              mut velocities: ViewMut<Velocity>| {
-                for _ in 0..1_000_000 {
+                for _ in 0..size {
                     entities.add_entity(
                         (
                             &mut transforms,
@@ -47,7 +53,7 @@ impl Benchmark {
         Self(world)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         self.0.run(
             |velocities: View<Velocity>, mut positions: ViewMut<Position>| {
                 (&velocities, &mut positions)
@@ -58,4 +64,13 @@ impl Benchmark {
             },
         ).unwrap();
     }
+
+    fn checksum(&mut self) -> f64 {
+        self.0.run(|positions: View<Position>| {
+            (&positions)
+                .iter()
+                .map(|position| position.0.x as f64)
+                .sum()
+        }).unwrap()
+    }
 }
@@ -1,5 +1,8 @@
 use specs::prelude::*;
 use specs_derive::*;
+
+use crate::EcsBenchmark;
+
 #[derive(Component)]
 #[storage(VecStorage)]
 struct A(f32);
@@ -9,18 +12,22 @@ struct B(f32);
 
 pub struct Benchmark(World, Vec<Entity>);
 
-impl Benchmark {
-    pub fn new() -> Self {
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
         let mut world = World::new();
         world.register::<A>();
         world.register::<B>();
-        let entities = (0..10000)
+        let entities = (0..size)
             .map(|_| world.create_entity().with(A(0.0)).build())
             .collect();
         Self(world, entities)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         let mut b_storage = self.0.write_storage::<B>();
         for entity in &self.1 {
             b_storage.insert(*entity, B(0.0)).unwrap();
@@ -30,4 +37,10 @@ impl Benchmark {
             b_storage.remove(*entity);
         }
     }
+
+    fn checksum(&mut self) -> f64 {
+        let a = self.0.read_storage::<A>().join().count();
+        let b = self.0.read_storage::<B>().join().count();
+        (a + b) as f64
+    }
 }
@@ -0,0 +1,47 @@
+use specs::prelude::*;
+use specs_derive::*;
+
+use crate::EcsBenchmark;
+
+#[derive(Component)]
+#[storage(VecStorage)]
+struct A(f32);
+#[derive(Component)]
+#[storage(VecStorage)]
+struct B(f32);
+
+pub struct Benchmark(World, Vec<Entity>);
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = World::new();
+        world.register::<A>();
+        world.register::<B>();
+        let entities = (0..size)
+            .map(|_| world.create_entity().with(A(0.0)).with(B(0.0)).build())
+            .collect();
+        Self(world, entities)
+    }
+
+    fn run(&mut self) {
+        let size = self.1.len();
+        for entity in self.1.drain(..) {
+            self.0.delete_entity(entity).unwrap();
+        }
+
+        for _ in 0..size {
+            let entity = self.0.create_entity().with(A(0.0)).with(B(0.0)).build();
+            self.1.push(entity);
+        }
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let a = self.0.read_storage::<A>().join().count();
+        let b = self.0.read_storage::<B>().join().count();
+        (a + b) as f64
+    }
+}
@@ -0,0 +1,137 @@
+use specs::prelude::*;
+use specs::shrev::EventChannel;
+use specs_derive::*;
+
+use crate::EcsBenchmark;
+
+#[derive(Component)]
+#[storage(VecStorage)]
+struct Body(u32);
+
+#[derive(Component)]
+#[storage(VecStorage)]
+struct Health(f32);
+
+#[derive(Component)]
+#[storage(VecStorage)]
+struct Contacts(u32);
+
+struct Collision {
+    a: u32,
+    b: u32,
+    impulse: f32,
+}
+
+fn impulse(body: u32) -> f32 {
+    1.0 + (body % 4) as f32
+}
+
+struct CollideSystem {
+    bodies: u32,
+}
+
+impl<'a> System<'a> for CollideSystem {
+    type SystemData = (ReadStorage<'a, Body>, Write<'a, EventChannel<Collision>>);
+
+    fn run(&mut self, (bodies, mut events): Self::SystemData) {
+        for body in bodies.join() {
+            events.single_write(Collision {
+                a: body.0,
+                b: (body.0 + 1) % self.bodies,
+                impulse: impulse(body.0),
+            });
+        }
+    }
+}
+
+struct DamageSystem {
+    reader: ReaderId<Collision>,
+    damage: Vec<f32>,
+}
+
+impl<'a> System<'a> for DamageSystem {
+    type SystemData = (
+        WriteStorage<'a, Health>,
+        ReadStorage<'a, Body>,
+        Read<'a, EventChannel<Collision>>,
+    );
+
+    fn run(&mut self, (mut healths, bodies, events): Self::SystemData) {
+        for collision in events.read(&mut self.reader) {
+            self.damage[collision.a as usize] += collision.impulse;
+            self.damage[collision.b as usize] += collision.impulse;
+        }
+        for (health, body) in (&mut healths, &bodies).join() {
+            health.0 -= std::mem::take(&mut self.damage[body.0 as usize]);
+        }
+    }
+}
+
+struct TallySystem {
+    reader: ReaderId<Collision>,
+    contacts: Vec<u32>,
+}
+
+impl<'a> System<'a> for TallySystem {
+    type SystemData = (
+        WriteStorage<'a, Contacts>,
+        ReadStorage<'a, Body>,
+        Read<'a, EventChannel<Collision>>,
+    );
+
+    fn run(&mut self, (mut contacts, bodies, events): Self::SystemData) {
+        for collision in events.read(&mut self.reader) {
+            self.contacts[collision.a as usize] += 1;
+            self.contacts[collision.b as usize] += 1;
+        }
+        for (contacts, body) in (&mut contacts, &bodies).join() {
+            contacts.0 += std::mem::take(&mut self.contacts[body.0 as usize]);
+        }
+    }
+}
+
+pub struct Benchmark<'a>(World, Dispatcher<'a, 'a>);
+
+impl EcsBenchmark for Benchmark<'static> {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = World::new();
+        world.register::<Body>();
+        world.register::<Health>();
+        world.register::<Contacts>();
+        (0..size).for_each(|i| {
+            world
+                .create_entity()
+                .with(Body(i as u32))
+                .with(Health(100.0))
+                .with(Contacts(0))
+                .build();
+        });
+
+        let mut events = EventChannel::<Collision>::with_capacity(size);
+        let damage = DamageSystem { reader: events.register_reader(), damage: vec![0.0; size] };
+        let tally = TallySystem { reader: events.register_reader(), contacts: vec![0; size] };
+        world.insert(events);
+
+        let dispatcher = DispatcherBuilder::new()
+            .with(CollideSystem { bodies: size as u32 }, "collide", &[])
+            .with(damage, "damage", &["collide"])
+            .with(tally, "tally", &["collide"])
+            .build();
+
+        Self(world, dispatcher)
+    }
+
+    fn run(&mut self) {
+        self.1.dispatch_par(&self.0)
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let health: f64 = self.0.read_storage::<Health>().join().map(|health| health.0 as f64).sum();
+        let contacts: f64 = self.0.read_storage::<Contacts>().join().map(|contacts| contacts.0 as f64).sum();
+        health + contacts
+    }
+}
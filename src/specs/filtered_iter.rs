@@ -0,0 +1,70 @@
+use cgmath::*;
+use specs::prelude::*;
+use specs_derive::*;
+
+use crate::EcsBenchmark;
+
+#[derive(Copy, Clone, Component)]
+#[storage(VecStorage)]
+struct Position(Vector3<f32>);
+
+#[derive(Copy, Clone, Component)]
+#[storage(VecStorage)]
+struct Velocity(Vector3<f32>);
+
+#[derive(Copy, Clone, Component)]
+#[storage(DenseVecStorage)]
+struct Drag(f32);
+
+#[derive(Copy, Clone, Default, Component)]
+#[storage(NullStorage)]
+struct Frozen;
+
+pub struct Benchmark(World);
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = World::new();
+        world.register::<Position>();
+        world.register::<Velocity>();
+        world.register::<Drag>();
+        world.register::<Frozen>();
+        for i in 0..size {
+            let mut entity = world
+                .create_entity()
+                .with(Position(Vector3::unit_x()))
+                .with(Velocity(Vector3::unit_x()));
+            if i % 3 == 0 {
+                entity = entity.with(Drag(0.5));
+            }
+            if i % 4 == 0 {
+                entity = entity.with(Frozen);
+            }
+            entity.build();
+        }
+
+        Self(world)
+    }
+
+    fn run(&mut self) {
+        let mut positions = self.0.write_storage::<Position>();
+        let velocities = self.0.read_storage::<Velocity>();
+        let drags = self.0.read_storage::<Drag>();
+        let frozen = self.0.read_storage::<Frozen>();
+        for (position, velocity, drag, ()) in (&mut positions, &velocities, drags.maybe(), !&frozen).join() {
+            position.0 += velocity.0 * (1.0 - drag.map_or(0.0, |drag| drag.0));
+        }
+    }
+
+    fn checksum(&mut self) -> f64 {
+        self.0
+            .read_storage::<Position>()
+            .join()
+            .map(|position| position.0.x as f64)
+            .sum()
+    }
+}
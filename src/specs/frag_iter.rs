@@ -1,14 +1,16 @@
 use specs::prelude::*;
 use specs_derive::*;
 
+use crate::EcsBenchmark;
+
 macro_rules! create_entities {
-    ($world:ident; $( $variants:ident ),*) => {
+    ($world:ident, $size:expr; $( $variants:ident ),*) => {
         $(
             #[derive(Component)]
             #[storage(VecStorage)]
             struct $variants(f32);
             $world.register::<$variants>();
-            (0..20)
+            (0..$size)
             .for_each(|_| {$world.create_entity().with($variants(0.0)).with(Data(1.0)).build();});
         )*
     };
@@ -30,16 +32,28 @@ impl<'a> System<'a> for FragIterSystem {
 }
 pub struct Benchmark(World, FragIterSystem);
 
-impl Benchmark {
-    pub fn new() -> Self {
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
         let mut world = World::new();
         world.register::<Data>();
-        create_entities!(world; A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z);
+        create_entities!(world, size; A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z);
 
         Self(world, FragIterSystem)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         self.1.run_now(&self.0)
     }
+
+    fn checksum(&mut self) -> f64 {
+        self.0
+            .read_storage::<Data>()
+            .join()
+            .map(|data| data.0 as f64)
+            .sum()
+    }
 }
@@ -3,6 +3,8 @@ use rayon::prelude::*;
 use specs::{prelude::*, ParJoin};
 use specs_derive::*;
 
+use crate::EcsBenchmark;
+
 #[derive(Copy, Clone, Component)]
 #[storage(VecStorage)]
 struct Transform(Matrix4<f32>);
@@ -38,14 +40,18 @@ impl<'a> System<'a> for HeavyComputeSystem {
 
 pub struct Benchmark(World, HeavyComputeSystem);
 
-impl Benchmark {
-    pub fn new() -> Self {
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
         let mut world = World::new();
         world.register::<Transform>();
         world.register::<Position>();
         world.register::<Rotation>();
         world.register::<Velocity>();
-        (0..1000).for_each(|_| {
+        (0..size).for_each(|_| {
             world
                 .create_entity()
                 .with(Transform(Matrix4::<f32>::from_angle_x(Rad(1.2))))
@@ -58,7 +64,15 @@ impl Benchmark {
         Self(world, HeavyComputeSystem)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         self.1.run_now(&self.0);
     }
+
+    fn checksum(&mut self) -> f64 {
+        self.0
+            .read_storage::<Position>()
+            .join()
+            .map(|position| (position.0.x + position.0.y + position.0.z) as f64)
+            .sum()
+    }
 }
@@ -0,0 +1,56 @@
+use cgmath::*;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use specs::prelude::*;
+use specs_derive::*;
+
+use crate::EcsBenchmark;
+
+#[derive(Copy, Clone, Component)]
+#[storage(VecStorage)]
+struct Position(Vector3<f32>);
+
+#[derive(Copy, Clone, Component)]
+#[storage(VecStorage)]
+struct Velocity(Vector3<f32>);
+
+pub struct Benchmark(World, Vec<Entity>, f64);
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
+        let mut world = World::new();
+        world.register::<Position>();
+        world.register::<Velocity>();
+        let mut entities = (0..size)
+            .map(|i| {
+                world
+                    .create_entity()
+                    .with(Position(Vector3::new(i as f32, 0.0, 0.0)))
+                    .with(Velocity(Vector3::unit_x()))
+                    .build()
+            })
+            .collect::<Vec<_>>();
+        entities.shuffle(&mut StdRng::seed_from_u64(0));
+
+        Self(world, entities, 0.0)
+    }
+
+    fn run(&mut self) {
+        let positions = self.0.read_storage::<Position>();
+        let velocities = self.0.read_storage::<Velocity>();
+        let mut sum = 0.0;
+        for &entity in &self.1 {
+            let position = positions.get(entity).unwrap();
+            let velocity = velocities.get(entity).unwrap();
+            sum += (position.0.x + velocity.0.x) as f64;
+        }
+        self.2 = sum;
+    }
+
+    fn checksum(&mut self) -> f64 {
+        self.2
+    }
+}
@@ -1,5 +1,8 @@
 use specs::prelude::*;
 use specs_derive::*;
+
+use crate::EcsBenchmark;
+
 #[derive(Component)]
 #[storage(VecStorage)]
 struct A(f32);
@@ -53,44 +56,48 @@ impl<'a> System<'a> for CESystem {
 
 pub struct Benchmark<'a>(World, Dispatcher<'a, 'a>);
 
-impl Benchmark<'_> {
-    pub fn new() -> Self {
+impl EcsBenchmark for Benchmark<'static> {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
         let mut world = World::new();
         world.register::<A>();
         world.register::<B>();
         world.register::<C>();
         world.register::<D>();
         world.register::<E>();
-        (0..10000).for_each(|_| {
-            world.create_entity().with(A(0.0)).build();
+        (0..size).for_each(|_| {
+            world.create_entity().with(A(1.0)).build();
         });
-        (0..10000).for_each(|_| {
-            world.create_entity().with(A(0.0)).with(B(0.0)).build();
+        (0..size).for_each(|_| {
+            world.create_entity().with(A(1.0)).with(B(2.0)).build();
         });
-        (0..10000).for_each(|_| {
+        (0..size).for_each(|_| {
             world
                 .create_entity()
-                .with(A(0.0))
-                .with(B(0.0))
-                .with(C(0.0))
+                .with(A(1.0))
+                .with(B(2.0))
+                .with(C(3.0))
                 .build();
         });
-        (0..10000).for_each(|_| {
+        (0..size).for_each(|_| {
             world
                 .create_entity()
-                .with(A(0.0))
-                .with(B(0.0))
-                .with(C(0.0))
-                .with(D(0.0))
+                .with(A(1.0))
+                .with(B(2.0))
+                .with(C(3.0))
+                .with(D(4.0))
                 .build();
         });
-        (0..10000).for_each(|_| {
+        (0..size).for_each(|_| {
             world
                 .create_entity()
-                .with(A(0.0))
-                .with(B(0.0))
-                .with(C(0.0))
-                .with(E(0.0))
+                .with(A(1.0))
+                .with(B(2.0))
+                .with(C(3.0))
+                .with(E(5.0))
                 .build();
         });
 
@@ -103,7 +110,15 @@ impl Benchmark<'_> {
         Self(world, dispatcher)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         self.1.dispatch_par(&self.0)
     }
+
+    fn checksum(&mut self) -> f64 {
+        let b: f32 = self.0.read_storage::<B>().join().map(|b| b.0).sum();
+        let c: f32 = self.0.read_storage::<C>().join().map(|c| c.0).sum();
+        let d: f32 = self.0.read_storage::<D>().join().map(|d| d.0).sum();
+        let e: f32 = self.0.read_storage::<E>().join().map(|e| e.0).sum();
+        (b + c + d + e) as f64
+    }
 }
@@ -2,6 +2,8 @@ use cgmath::*;
 use specs::prelude::*;
 use specs_derive::*;
 
+use crate::EcsBenchmark;
+
 #[derive(Copy, Clone, Component)]
 #[storage(VecStorage)]
 struct Transform(Matrix4<f32>);
@@ -17,20 +19,24 @@ struct Rotation(Vector3<f32>);
 #[storage(VecStorage)]
 struct Velocity(Vector3<f32>);
 
-pub struct Benchmark;
+pub struct Benchmark(usize, Option<World>);
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
 
-impl Benchmark {
-    pub fn new() -> Self {
-        Self
+    fn setup(size: usize) -> Self {
+        Self(size, None)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         let mut world = World::new();
         world.register::<Transform>();
         world.register::<Position>();
         world.register::<Rotation>();
         world.register::<Velocity>();
-        (0..1_000_000).for_each(|_| {
+        (0..self.0).for_each(|_| {
             world
                 .create_entity()
                 .with(Transform(Matrix4::<f32>::from_scale(1.0)))
@@ -39,5 +45,20 @@ impl Benchmark {
                 .with(Velocity(Vector3::unit_x()))
                 .build();
         });
+
+        self.1 = Some(world);
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let world = self.1.as_ref().unwrap();
+        world
+            .read_storage::<Position>()
+            .join()
+            .map(|position| position.0.x as f64)
+            .sum()
+    }
+
+    fn teardown(&mut self) {
+        self.1 = None;
     }
 }
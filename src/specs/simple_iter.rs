@@ -2,6 +2,8 @@ use cgmath::*;
 use specs::prelude::*;
 use specs_derive::*;
 
+use crate::EcsBenchmark;
+
 #[derive(Copy, Clone, Component)]
 #[storage(VecStorage)]
 struct Transform(Matrix4<f32>);
@@ -29,14 +31,18 @@ impl<'a> System<'a> for SimpleIterSystem {
 }
 pub struct Benchmark(World, SimpleIterSystem);
 
-impl Benchmark {
-    pub fn new() -> Self {
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
         let mut world = World::new();
         world.register::<Transform>();
         world.register::<Position>();
         world.register::<Rotation>();
         world.register::<Velocity>();
-        (0..1_000_000).for_each(|_| {
+        (0..size).for_each(|_| {
             world
                 .create_entity()
                 .with(Transform(Matrix4::<f32>::from_angle_x(Rad(1.2))))
@@ -49,7 +55,15 @@ impl Benchmark {
         Self(world, SimpleIterSystem)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         self.1.run_now(&self.0);
     }
+
+    fn checksum(&mut self) -> f64 {
+        self.0
+            .read_storage::<Position>()
+            .join()
+            .map(|position| position.0.x as f64)
+            .sum()
+    }
 }
@@ -1,2 +1,4 @@
 pub mod simple_insert;
 pub mod simple_iter;
+
+pub const NAME: &str = "tribles";
@@ -3,6 +3,8 @@ use std::collections::HashMap;
 use cgmath::*;
 use tribles::id::fucid;
 
+use crate::EcsBenchmark;
+
 #[derive(Copy, Clone)]
 struct Transform(Matrix4<f32>);
 #[derive(Copy, Clone)]
@@ -14,25 +16,48 @@ struct Rotation(Vector3<f32>);
 #[derive(Copy, Clone)]
 struct Velocity(Vector3<f32>);
 
-pub struct Benchmark;
+pub struct Benchmark(
+    usize,
+    Option<(
+        HashMap<[u8; 16], Transform>,
+        HashMap<[u8; 16], Position>,
+        HashMap<[u8; 16], Rotation>,
+        HashMap<[u8; 16], Velocity>,
+    )>,
+);
+
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
 
-impl Benchmark {
-    pub fn new() -> Self {
-        Self
+    fn setup(size: usize) -> Self {
+        Self(size, None)
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
         let mut tf = HashMap::new();
         let mut pos = HashMap::new();
         let mut rot = HashMap::new();
         let mut vel = HashMap::new();
         
-        (0..1_000_000).for_each(|entity: u128| {
+        (0..self.0 as u128).for_each(|entity: u128| {
             let entity = entity.to_be_bytes();
             tf.insert(entity, Transform(Matrix4::<f32>::from_scale(1.0)));
             pos.insert(entity, Position(Vector3::unit_x()));
             rot.insert(entity, Rotation(Vector3::unit_x()));
             vel.insert(entity, Velocity(Vector3::unit_x()));
         });
+
+        self.1 = Some((tf, pos, rot, vel));
+    }
+
+    fn checksum(&mut self) -> f64 {
+        let (_, pos, _, _) = self.1.as_ref().unwrap();
+        pos.values().map(|position| position.0.x as f64).sum()
+    }
+
+    fn teardown(&mut self) {
+        self.1 = None;
     }
 }
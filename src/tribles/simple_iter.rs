@@ -3,6 +3,8 @@ use std::{cell::Cell, collections::HashMap};
 use cgmath::*;
 use tribles::{and, find, id::fucid, query::ContainsConstraint, RawId};
 
+use crate::EcsBenchmark;
+
 #[derive(Copy, Clone)]
 struct Transform(Matrix4<f32>);
 #[derive(Copy, Clone)]
@@ -21,14 +23,18 @@ pub struct Benchmark{
     vel: HashMap<RawId, Cell<Velocity>>
 }
 
-impl Benchmark {
-    pub fn new() -> Self {
+impl EcsBenchmark for Benchmark {
+    fn name() -> &'static str {
+        super::NAME
+    }
+
+    fn setup(size: usize) -> Self {
         let mut tf = HashMap::new();
         let mut pos = HashMap::new();
         let mut rot = HashMap::new();
         let mut vel = HashMap::new();
         
-        (0..1_000_000).for_each(|_| {
+        (0..size).for_each(|_| {
             let entity = fucid();
             tf.insert(entity, Transform(Matrix4::<f32>::from_angle_x(Rad(1.2))).into());
             pos.insert(entity, Position(Vector3::unit_x()).into());
@@ -39,7 +45,7 @@ impl Benchmark {
         Self{tf, pos, rot, vel}
     }
 
-    pub fn run(&mut self) {
+    fn run(&mut self) {
 
         for (entity,) in find!(ctx, (entity), and!(
             self.pos.has(entity),
@@ -52,4 +58,8 @@ impl Benchmark {
                 pos_cell.set(pos);
         }
     }
+
+    fn checksum(&mut self) -> f64 {
+        self.pos.values().map(|position| position.get().0.x as f64).sum()
+    }
 }